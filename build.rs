@@ -0,0 +1,79 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exposes build metadata to the crate as `SUIUP_*` environment variables, used by
+//! `suiup self version`.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[path = "src/date.rs"]
+mod date;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/date.rs");
+    watch_git_head();
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let git_sha = command_output("git", &["rev-parse", "--short", "HEAD"])
+        .unwrap_or_else(|| "unknown".to_string());
+    let rustc = command_output(
+        &env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()),
+        &["--version"],
+    )
+    .unwrap_or_else(|| "unknown".to_string());
+    let target = env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+
+    println!("cargo:rustc-env=SUIUP_GIT_SHA={git_sha}");
+    println!("cargo:rustc-env=SUIUP_BUILD_DATE={}", build_date());
+    println!("cargo:rustc-env=SUIUP_TARGET={target}");
+    println!("cargo:rustc-env=SUIUP_RUSTC={rustc}");
+}
+
+/// Reruns the build script when a commit changes `HEAD`: `.git/HEAD` only changes when switching
+/// branches, a commit updates the branch ref it points to, or `.git/packed-refs` once refs are
+/// packed. Only existing files are watched, as cargo reruns the script for missing ones every
+/// build.
+fn watch_git_head() {
+    let head = Path::new(".git/HEAD");
+    let mut watched = vec![
+        head.to_path_buf(),
+        Path::new(".git/packed-refs").to_path_buf(),
+    ];
+    if let Some(reference) = std::fs::read_to_string(head)
+        .ok()
+        .and_then(|head| Some(head.strip_prefix("ref:")?.trim().to_string()))
+    {
+        watched.push(Path::new(".git").join(reference));
+    }
+    for path in watched.iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!output.is_empty()).then_some(output)
+}
+
+/// Returns the build date as YYYY-MM-DD, honoring SOURCE_DATE_EPOCH for reproducible builds
+fn build_date() -> String {
+    let secs = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default()
+        });
+
+    date::format_date(secs)
+}
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::alias::handle_alias_list;
+
+/// List the aliases and the specs they stand for.
+#[derive(Args, Debug)]
+pub struct Command;
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_alias_list()
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+mod list;
+mod remove;
+mod set;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Name binary specs (e.g. 'my-stable' for 'sui@testnet-1.39.3') to use the name wherever a spec
+/// is expected.
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    List(list::Command),
+    Remove(remove::Command),
+    Set(set::Command),
+}
+
+impl Command {
+    /// Whether the command leaves the config as it is
+    pub fn is_read_only(&self) -> bool {
+        matches!(self.command, Commands::List(_))
+    }
+
+    /// Handles the alias commands
+    pub fn exec(&self) -> Result<()> {
+        match &self.command {
+            Commands::List(cmd) => cmd.exec(),
+            Commands::Remove(cmd) => cmd.exec(),
+            Commands::Set(cmd) => cmd.exec(),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::alias::handle_alias_remove;
+
+/// Remove an alias. The binary it stands for stays installed.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Alias to remove (e.g. 'my-stable')
+    name: String,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_alias_remove(&self.name)
+    }
+}
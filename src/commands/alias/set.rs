@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::alias::handle_alias_set;
+
+/// Add an alias for a binary spec, or change the spec of an existing one
+/// (e.g. 'suiup alias set my-stable sui@testnet-1.39.3').
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Name of the alias (letters, digits, '.', '_' and '-'), which cannot be a binary name
+    name: String,
+
+    /// Binary spec the alias stands for (e.g. 'sui@testnet-1.39.3', 'mvr@0.0.8')
+    spec: String,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_alias_set(&self.name, &self.spec)
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+mod warm;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Manage the release archives and binaries cached by suiup.
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    Warm(warm::Command),
+}
+
+impl Command {
+    /// Handles the cache commands
+    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+        match &self.command {
+            Commands::Warm(cmd) => cmd.exec(github_token).await,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::cache::handle_cache_warm;
+
+/// Download binaries ahead of time, without making them the default, so that installing them
+/// later works offline (e.g. 'suiup cache warm sui@testnet-1.40.1 mvr@0.0.5').
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Binaries to download, with optional version (e.g. 'sui@testnet-1.40.1', 'mvr@0.0.5')
+    #[arg(required = true, num_args = 1..)]
+    components: Vec<String>,
+}
+
+impl Command {
+    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+        handle_cache_warm(&self.components, github_token.clone()).await
+    }
+}
@@ -1,33 +1,71 @@
 use anyhow::Result;
-use clap::Args;
+use clap::{ArgGroup, Args};
 
 use crate::handle_commands::handle_cmd;
 
-use super::ComponentCommands;
+use super::{BinaryName, ComponentCommands};
 
 /// Remove old release archives from the cache directory.
 #[derive(Args, Debug)]
+#[command(group(ArgGroup::new("binaries_filter").args(["network", "component"]).multiple(true)))]
 pub struct Command {
-    /// Days to keep files in cache
-    #[clap(long, short = 'd', default_value = "30")]
-    days: u32,
+    /// Days to keep files in cache (default: 30). With --network, only together with --component
+    #[clap(long, short = 'd')]
+    days: Option<u32>,
 
     /// Remove all cache files
     #[clap(long, conflicts_with = "days")]
     all: bool,
 
+    /// Also remove the cached release lists and their ETags, so they are fetched again (implied
+    /// by --all)
+    #[clap(long)]
+    caches: bool,
+
+    /// Keep only the N newest archives per binary and network, regardless of age
+    #[clap(long, conflicts_with_all = ["all", "days"])]
+    keep: Option<usize>,
+
+    /// Network (or nightly branch) whose extracted binaries and archives to remove, with --binaries
+    #[clap(long, requires = "binaries", conflicts_with_all = ["all", "keep"])]
+    network: Option<String>,
+
+    /// Only remove extracted binaries of this component older than --days, keeping its default
+    /// version, with --binaries
+    #[clap(long, value_enum, requires = "binaries", conflicts_with_all = ["all", "keep"])]
+    component: Option<BinaryName>,
+
+    /// Remove extracted binaries of --network and/or --component, not only release archives
+    #[clap(long, requires = "binaries_filter")]
+    binaries: bool,
+
     /// Show what would be removed without actually removing anything
     #[clap(long, short = 'n')]
     dry_run: bool,
+
+    /// Only print the cache size before and after, and the number and size of the removed files,
+    /// instead of a line per file
+    #[clap(long)]
+    summary: bool,
 }
 
 impl Command {
+    pub fn is_read_only(&self) -> bool {
+        self.dry_run
+    }
+
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
         handle_cmd(
             ComponentCommands::Cleanup {
                 all: self.all,
                 days: self.days,
+                keep: self.keep,
+                caches: self.caches,
+                network: self.network.clone(),
+                component: self.component.clone(),
+                binaries: self.binaries,
                 dry_run: self.dry_run,
+                summary: self.summary,
             },
             github_token.to_owned(),
         )
@@ -0,0 +1,25 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::{Args, CommandFactory};
+use clap_complete::Shell;
+
+use crate::handlers::completion::handle_completion;
+
+/// Generate shell completions, printing them or installing them for the shell to pick up.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Shell to generate completions for
+    shell: Shell,
+
+    /// Write the completions to the shell's completion directory instead of printing them
+    #[arg(long)]
+    install: bool,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_completion(self.shell, self.install, &mut super::Command::command())
+    }
+}
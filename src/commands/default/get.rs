@@ -1,30 +1,90 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
-
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Args;
+use std::process::Command as Process;
 
-use crate::{
-    paths::default_file_path,
-    types::{Binaries, Version},
+use crate::handlers::show::load_default_binaries;
+use crate::handlers::{
+    default_binary_source,
+    version::{reported_version, version_matches, VERSION_TIMEOUT},
 };
+use crate::paths::{display_path, get_default_bin_dir};
+use crate::symbols::symbols;
+use crate::types::BinaryVersion;
 
 use crate::commands::print_table;
 
-/// Get the default Sui CLI version.
+/// Get the default version of a binary.
 #[derive(Args, Debug)]
-pub struct Command;
+pub struct Command {
+    /// Binary to query (e.g. 'sui', 'mvr'). Omitting it lists all defaults, which is deprecated
+    /// in favor of `suiup default list`.
+    binary: Option<String>,
+
+    /// Also show the file that runs, the installed file it was copied from, and the version it
+    /// reports with `--version`, flagging a mismatch with the recorded version
+    #[arg(long, requires = "binary")]
+    resolve: bool,
+}
 
 impl Command {
     pub fn exec(&self) -> Result<()> {
-        let default = std::fs::read_to_string(default_file_path()?)?;
-        let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&default)?;
-        let binaries = Binaries::from(default);
+        let Some(binary) = &self.binary else {
+            eprintln!("Hint: `suiup default get` without a binary is deprecated, use `suiup default list` instead.");
+            return super::list::Command.exec();
+        };
 
-        println!("\x1b[1mDefault binaries:\x1b[0m");
-        print_table(&binaries.binaries);
+        let binaries: Vec<_> = load_default_binaries()?
+            .binaries
+            .into_iter()
+            .filter(|b| &b.binary_name == binary)
+            .collect();
+        if binaries.is_empty() {
+            bail!("No default version set for {binary}. Use `suiup default list` to see all defaults.");
+        }
+
+        print_table(&binaries);
+        if self.resolve {
+            for binary in &binaries {
+                resolve(binary);
+            }
+        }
         Ok(())
     }
 }
+
+/// Prints what runs for a default binary: the file in the default bin folder, the installed file
+/// it comes from and the version it reports
+fn resolve(binary: &BinaryVersion) {
+    let path = get_default_bin_dir().join(&binary.binary_name);
+    #[cfg(windows)]
+    let path = path.with_extension("exe");
+    let source = default_binary_source(binary);
+    let missing = |path: &std::path::Path| if path.is_file() { "" } else { " (missing)" };
+
+    println!("Runs:     {}{}", display_path(&path), missing(&path));
+    println!("Source:   {}{}", display_path(&source), missing(&source));
+    if !path.is_file() {
+        return;
+    }
+
+    let mut cmd = Process::new(&path);
+    cmd.arg("--version");
+    match reported_version(cmd, VERSION_TIMEOUT) {
+        Ok(reported) if version_matches(&reported, &binary.version) => {
+            println!("Reports:  {reported}")
+        }
+        Ok(reported) => {
+            println!("Reports:  {reported}");
+            eprintln!(
+                "[{}] {} reports '{reported}', but version {} is the default",
+                symbols().warn,
+                binary.binary_name,
+                binary.version
+            );
+        }
+        Err(e) => eprintln!("[{}] {} {e}", symbols().warn, binary.binary_name),
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::show::load_default_binaries;
+
+use crate::commands::print_table;
+
+/// List the default version of every binary.
+#[derive(Args, Debug)]
+pub struct Command;
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        let binaries = load_default_binaries()?;
+
+        println!("\x1b[1mDefault binaries:\x1b[0m");
+        print_table(&binaries.binaries);
+        Ok(())
+    }
+}
@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod get;
+mod list;
 mod set;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
-/// Get or set the default tool version.
+/// Get, list or set the default tool version.
 #[derive(Debug, Args)]
 pub struct Command {
     #[command(subcommand)]
@@ -17,14 +18,21 @@ pub struct Command {
 #[derive(Subcommand, Debug)]
 enum Commands {
     Get(get::Command),
+    List(list::Command),
     Set(set::Command),
 }
 
 impl Command {
+    /// Whether the command leaves the installed binaries and the config as they are
+    pub fn is_read_only(&self) -> bool {
+        !matches!(self.command, Commands::Set(_))
+    }
+
     /// Handles the default commands
     pub fn exec(&self) -> Result<()> {
         match &self.command {
             Commands::Get(cmd) => cmd.exec(),
+            Commands::List(cmd) => cmd.exec(),
             Commands::Set(cmd) => cmd.exec(),
         }
     }
@@ -6,13 +6,18 @@ use clap::Args;
 use tracing::{debug, info};
 
 use crate::{
-    commands::{parse_component_with_version, BinaryName, CommandMetadata},
-    handlers::{installed_binaries_grouped_by_network, update_default_version_file},
-    paths::{binaries_dir, get_default_bin_dir},
+    commands::{
+        parse_component_with_version, resolve_alias, spec_network, BinaryName, CommandMetadata,
+    },
+    handlers::{
+        confirm_mainnet, installed_binaries_grouped_by_network, installed_binary_path,
+        place_companions, place_default_binary, remember_network, switch::handle_set_all_latest,
+        update_default_version_file, version::compare_versions,
+    },
+    paths::get_default_bin_dir,
+    types::{BinaryVersion, Config, NetworkOrBranch},
 };
-
-#[cfg(not(windows))]
-use std::os::unix::fs::PermissionsExt;
+use std::collections::BTreeMap;
 
 /// Set the default Sui CLI version.
 #[derive(Args, Debug)]
@@ -20,8 +25,10 @@ pub struct Command {
     /// Binary to be set as default and the version
     /// e.g. 'sui@testnet-1.39.3', 'sui@testnet' --
     /// this will use an installed binary
-    /// that has the highest testnet version)
-    name: String,
+    /// that has the highest testnet version), or 'sui@latest' for the highest installed
+    /// version of any network
+    #[arg(required_unless_present = "all_latest")]
+    name: Option<String>,
 
     /// Whether to set the debug version of the binary as default (only available for sui).
     #[arg(long)]
@@ -31,6 +38,14 @@ pub struct Command {
     /// Use `suiup show` to find all installed binaries
     #[arg(long, value_name = "branch", default_missing_value = "main", num_args = 0..=1)]
     nightly: Option<String>,
+
+    /// Skip the mainnet confirmation (when `confirm_mainnet` is enabled in the config)
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Set every default to the newest installed version of its current network
+    #[arg(long, conflicts_with_all = ["name", "debug", "nightly"])]
+    all_latest: bool,
 }
 
 impl Command {
@@ -39,8 +54,18 @@ impl Command {
             name,
             debug,
             nightly,
+            yes,
+            all_latest,
         } = self;
 
+        if *all_latest {
+            return handle_set_all_latest(*yes);
+        }
+        let config = Config::load()?;
+        let name = resolve_alias(name.as_deref().unwrap_or_default(), &config);
+        let name = name.as_str();
+        let explicit_network = spec_network(name).filter(|_| nightly.is_none());
+
         if name.is_empty() && nightly.is_none() {
             bail!("Invalid number of arguments. Version is required: 'sui@testnet-1.39.3', 'sui@testnet' -- this will use an installed binary that has the highest testnet version. \n For `mvr` only pass the version: `mvr@0.0.5`")
         }
@@ -49,7 +74,9 @@ impl Command {
             name,
             network,
             version,
-        } = parse_component_with_version(name)?;
+        } = parse_component_with_version(name, &config)?;
+        let binary = name.to_string();
+        let latest = version.as_deref() == Some("latest") && nightly.is_none();
 
         let network = if name == BinaryName::Mvr {
             if let Some(ref nightly) = nightly {
@@ -65,6 +92,16 @@ impl Command {
 
         // a map of network --> to BinaryVersion
         let installed_binaries = installed_binaries_grouped_by_network(None)?;
+        let (network, version) = if latest {
+            let (network, version) = latest_installed(&installed_binaries, &binary, *debug)?;
+            (network, Some(version))
+        } else {
+            (network.to_string(), version)
+        };
+        let network = network.as_str();
+
+        confirm_mainnet(network, "set a mainnet binary as default", *yes)?;
+
         let binaries = installed_binaries
             .get(network)
             .ok_or_else(|| anyhow!("No binaries installed for {network}"))?;
@@ -86,8 +123,10 @@ impl Command {
         } else {
             binaries
                 .iter()
-                .filter(|b| b.binary_name == name.to_string())
-                .max_by(|a, b| a.version.cmp(&b.version))
+                .filter(|b| {
+                    b.binary_name == name.to_string() && b.debug == *debug && b.target.is_none()
+                })
+                .max_by(|a, b| compare_versions(&a.version, &b.version))
                 .map(|b| b.version.clone())
                 .ok_or_else(|| anyhow!("No version found for {name} in {network}"))?
         };
@@ -95,10 +134,14 @@ impl Command {
         // check if the binary for this network and version exists
         let binary_version = format!("{}-{}", name, version);
         debug!("Checking if {binary_version} exists");
-        binaries
+        let installed = binaries
         .iter()
         .find(|b| {
-            b.binary_name == name.to_string() && b.version == version && b.network_release == network
+            b.binary_name == name.to_string()
+                && b.version == version
+                && b.network_release == network
+                && b.debug == *debug
+                && b.target.is_none()
         })
         .ok_or_else(|| {
             anyhow!("Binary {binary_version} from {network} release not found. Use `suiup show` to see installed binaries.")
@@ -117,52 +160,11 @@ impl Command {
         #[cfg(target_os = "windows")]
         dst.set_extension("exe");
 
-        let mut src = binaries_dir();
-        src.push(network);
-
-        if nightly.is_some() {
-            // cargo install adds a bin folder to the specified path :-)
-            src.push("bin");
-        }
-
-        if *debug {
-            src.push(format!("{}-debug-{}", name, version));
-        } else {
-            src.push(binary_version);
-        }
-
+        let src = installed_binary_path(&name, network, &version);
         info!("File source: {}", src.display());
 
-        #[cfg(target_os = "windows")]
-        let filename = src.file_name().expect("Expected binary filename");
-        #[cfg(target_os = "windows")]
-        src.set_file_name(format!(
-            "{}.exe",
-            filename
-                .to_str()
-                .expect("Expected binary filename as string")
-        ));
-
-        #[cfg(not(target_os = "windows"))]
-        {
-            if dst.exists() {
-                std::fs::remove_file(&dst)?;
-            }
-
-            std::fs::copy(&src, &dst)?;
-
-            #[cfg(unix)]
-            {
-                let mut perms = std::fs::metadata(&dst)?.permissions();
-                perms.set_mode(0o755);
-                std::fs::set_permissions(&dst, perms)?;
-            }
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            std::fs::copy(&src, &dst)?;
-        }
+        place_default_binary(&src, &dst, &name)?;
+        place_companions(installed)?;
 
         update_default_version_file(
             &vec![name.to_string()],
@@ -170,8 +172,29 @@ impl Command {
             &version,
             *debug,
         )?;
+        if let Some(network) = explicit_network {
+            remember_network(&binary, &network)?;
+        }
 
         println!("Default binary updated successfully");
         Ok(())
     }
 }
+
+/// Network and version of the highest installed release of `binary` (its debug build with
+/// `debug`), across all networks. Nightly builds and binaries downloaded for another platform are
+/// left out.
+fn latest_installed(
+    installed_binaries: &BTreeMap<String, Vec<BinaryVersion>>,
+    binary: &str,
+    debug: bool,
+) -> Result<(String, String)> {
+    installed_binaries
+        .values()
+        .flatten()
+        .filter(|b| !matches!(b.network(), NetworkOrBranch::Branch(_)))
+        .filter(|b| b.binary_name == binary && b.debug == debug && b.target.is_none())
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+        .map(|b| (b.network_release.clone(), b.version.clone()))
+        .ok_or_else(|| anyhow!("No installed release of {binary} found"))
+}
@@ -8,11 +8,30 @@ use crate::component::ComponentManager;
 
 /// Run diagnostic checks on the environment.
 #[derive(Args, Debug)]
-pub struct Command {}
+pub struct Command {
+    /// Remove default entries whose binaries are missing on disk
+    #[arg(long)]
+    fix: bool,
+
+    /// Also check the free space where binaries and release archives are stored
+    #[arg(long)]
+    check_disk: bool,
+
+    /// Also check that GitHub accepts the configured token, and its remaining request budget
+    #[arg(long)]
+    check_token: bool,
+}
 
 impl Command {
+    /// Whether the checks only report problems, without `--fix`
+    pub fn is_read_only(&self) -> bool {
+        !self.fix
+    }
+
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
         let component_manager = ComponentManager::new(github_token.clone());
-        component_manager.run_doctor_checks().await
+        component_manager
+            .run_doctor_checks(self.fix, self.check_disk, self.check_token)
+            .await
     }
 }
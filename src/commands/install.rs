@@ -1,19 +1,33 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
 use clap::Args;
+use regex::Regex;
 
 use crate::handle_commands::handle_cmd;
+use crate::handlers::batch::BatchResult;
+use crate::handlers::download::{set_asset_pattern, set_quiet_progress};
+use crate::handlers::install::{parse_manifest, print_download_url};
+use crate::handlers::{confirm_mainnet, remember_network};
+use crate::types::Config;
 
-use super::ComponentCommands;
+use super::{parse_component_with_version, resolve_pinned_spec, spec_network, ComponentCommands};
 
 /// Install a binary.
 #[derive(Args, Debug)]
 pub struct Command {
     /// Binary to install with optional version
     /// (e.g. 'sui', 'sui@1.40.1', 'sui@testnet', 'sui@testnet-1.39.3')
-    component: String,
+    #[arg(required_unless_present = "manifest")]
+    component: Option<String>,
+
+    /// Install every binary listed in a file, one spec per line (e.g. 'sui@testnet-1.40.1').
+    /// Blank lines and '#' comments are ignored.
+    #[arg(long, value_name = "path", conflicts_with_all = ["component", "nightly", "binary_name"])]
+    manifest: Option<PathBuf>,
 
     /// Install from a branch in release mode (use --debug for debug mode).
     /// If none provided, main is used. Note that this requires Rust & cargo to be installed.
@@ -29,19 +43,163 @@ pub struct Command {
     /// Accept defaults without prompting
     #[arg(short, long)]
     yes: bool,
+
+    /// Install the binary into the default bin folder under a custom name (e.g. 'sui-test'),
+    /// next to the managed one. The default version is left untouched.
+    #[arg(long, value_name = "name")]
+    binary_name: Option<String>,
+
+    /// Verify the downloaded file against this checksum ('sha256:<hex>' or 'md5:<hex>').
+    /// The file is deleted if it does not match.
+    #[arg(long, value_name = "algo:hex", conflicts_with_all = ["nightly", "manifest"])]
+    checksum: Option<String>,
+
+    /// Also install these companion binaries from the same release archive
+    /// (e.g. 'sui-tool,sui-faucet'), without downloading it again
+    #[arg(
+        long,
+        value_name = "binaries",
+        value_delimiter = ',',
+        conflicts_with_all = ["nightly", "manifest"]
+    )]
+    also: Vec<String>,
+
+    /// Download the binary for another platform (e.g. 'linux-x86_64', 'macos-aarch64') into the
+    /// data folder, without running it or making it the default. Useful to build bundles.
+    #[arg(long, value_name = "os-arch", conflicts_with_all = ["nightly", "binary_name"])]
+    target: Option<String>,
+
+    /// Install the binary without making it the default one
+    #[arg(long)]
+    no_default: bool,
+
+    /// With --manifest, stop at the first binary that fails instead of going on with the
+    /// remaining ones. suiup exits with the number of failed binaries.
+    #[arg(long, requires = "manifest")]
+    fail_fast: bool,
+
+    /// Print a plain 'Downloaded 50% (106/212 MB)' line at most once per second instead of the
+    /// animated progress bar, e.g. for logs or terminals that render it poorly
+    #[arg(long)]
+    quiet_progress: bool,
+
+    /// Only resolve the release asset and print its download URL (on the last line), without
+    /// downloading or installing anything
+    #[arg(
+        long,
+        conflicts_with_all = ["manifest", "nightly", "binary_name", "checksum", "also", "no_default"]
+    )]
+    print_only: bool,
+
+    /// Download the release asset whose name matches this regex (e.g. 'linux.*musl') instead of
+    /// the one picked for the OS and architecture. Fails unless exactly one asset matches.
+    #[arg(long, value_name = "regex", conflicts_with_all = ["manifest", "nightly"])]
+    asset_pattern: Option<String>,
+
+    /// Write the version the spec resolved to (e.g. 'testnet-v1.40.1' for 'sui@testnet') to this
+    /// file after installing, e.g. to key CI caches on it or to pin a later install
+    #[arg(
+        long,
+        value_name = "path",
+        conflicts_with_all = ["manifest", "nightly", "print_only"]
+    )]
+    write_version_file: Option<PathBuf>,
 }
 
 impl Command {
+    /// Whether the command only prints the download URL
+    pub fn is_read_only(&self) -> bool {
+        self.print_only
+    }
+
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+        set_quiet_progress(self.quiet_progress);
+        if let Some(pattern) = &self.asset_pattern {
+            let pattern = Regex::new(pattern)
+                .map_err(|e| anyhow!("Invalid --asset-pattern '{pattern}': {e}"))?;
+            set_asset_pattern(pattern);
+        }
+        if let Some(manifest) = &self.manifest {
+            return self.install_manifest(manifest, github_token).await;
+        }
+        let component = self.component.clone().unwrap_or_default();
+        if self.print_only {
+            let config = Config::load()?;
+            let metadata =
+                parse_component_with_version(&resolve_pinned_spec(&component, &config)?, &config)?;
+            return print_download_url(
+                &metadata.name,
+                &metadata.network,
+                metadata.version,
+                github_token.to_owned(),
+                self.target.to_owned(),
+            )
+            .await;
+        }
+        self.install(component, self.nightly.to_owned(), github_token)
+            .await
+    }
+
+    async fn install(
+        &self,
+        component: String,
+        nightly: Option<String>,
+        github_token: &Option<String>,
+    ) -> Result<()> {
+        let (component, remember) = if nightly.is_none() {
+            let config = Config::load()?;
+            let component = resolve_pinned_spec(&component, &config)?;
+            let metadata = parse_component_with_version(&component, &config)?;
+            confirm_mainnet(&metadata.network, "install a mainnet binary", self.yes)?;
+            let remember =
+                spec_network(&component).map(|network| (metadata.name.to_string(), network));
+            (component, remember)
+        } else {
+            (component, None)
+        };
         handle_cmd(
             ComponentCommands::Add {
-                component: self.component.to_owned(),
-                nightly: self.nightly.to_owned(),
+                component,
+                nightly,
                 debug: self.debug.to_owned(),
                 yes: self.yes.to_owned(),
+                binary_name: self.binary_name.to_owned(),
+                checksum: self.checksum.to_owned(),
+                also: self.also.to_owned(),
+                target: self.target.to_owned(),
+                no_default: self.no_default,
+                version_file: self.write_version_file.to_owned(),
             },
             github_token.to_owned(),
         )
-        .await
+        .await?;
+        if let Some((binary, network)) =
+            remember.filter(|_| !self.no_default && self.target.is_none())
+        {
+            remember_network(&binary, &network)?;
+        }
+        Ok(())
+    }
+
+    async fn install_manifest(&self, manifest: &Path, github_token: &Option<String>) -> Result<()> {
+        let content = std::fs::read_to_string(manifest)
+            .map_err(|e| anyhow!("Cannot read manifest {}: {e}", manifest.display()))?;
+        let specs = parse_manifest(&content, &Config::load()?)?;
+
+        let mut batch = BatchResult::new("install", !self.fail_fast);
+        for spec in &specs {
+            println!("Installing {spec}");
+            let result = self.install(spec.clone(), None, github_token).await;
+            if !batch.record(spec, result) {
+                break;
+            }
+        }
+
+        println!(
+            "Installed {} of {} binaries from the manifest",
+            batch.succeeded(),
+            specs.len()
+        );
+        batch.finish()
     }
 }
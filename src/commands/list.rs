@@ -10,10 +10,26 @@ use super::ComponentCommands;
 
 /// List available binaries to install.
 #[derive(Args, Debug)]
-pub struct Command;
+pub struct Command {
+    /// Print the binaries, their repositories and networks as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Print everything at once instead of through $PAGER when the output does not fit on the
+    /// screen
+    #[arg(long)]
+    no_pager: bool,
+}
 
 impl Command {
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
-        handle_cmd(ComponentCommands::List, github_token.to_owned()).await
+        handle_cmd(
+            ComponentCommands::List {
+                json: self.json,
+                no_pager: self.no_pager,
+            },
+            github_token.to_owned(),
+        )
+        .await
     }
 }
@@ -1,25 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod alias;
+mod cache;
 mod cleanup;
+mod completion;
 mod default;
 mod doctor;
 mod install;
 mod list;
 #[cfg(feature = "nix-patchelf")]
 mod patch;
+mod prune;
 mod remove;
+mod run;
 mod self_;
 mod show;
 mod switch;
 mod update;
 mod which;
 
-use crate::{handlers::self_::check_for_updates, types::BinaryVersion};
+use crate::{
+    handlers::{
+        default_binary_exists, release::set_offline_first, self_::check_for_updates,
+        set_assumed_answers, set_binary_layout, version::read_version_file,
+    },
+    paths::{initialize, set_dir_overrides, set_path_style, DirOverrides, PathStyle},
+    symbols::set_ascii,
+    types::{BinaryVersion, Config, Repo, NETWORKS},
+};
 
 use anyhow::{anyhow, bail, Result};
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use comfy_table::Table;
+use std::path::PathBuf;
 pub const TABLE_FORMAT: &str = "  ── ══      ──    ";
 #[derive(Parser)]
 #[command(arg_required_else_help = true, disable_help_subcommand = true)]
@@ -28,22 +42,83 @@ pub struct Command {
     #[command(subcommand)]
     command: Commands,
 
-    /// GitHub API token for authenticated requests (helps avoid rate limits).
+    /// GitHub API token for authenticated requests (helps avoid rate limits). Falls back to
+    /// `GH_TOKEN`, as exported by the GitHub CLI, when `GITHUB_TOKEN` is not set.
     #[arg(long, env = "GITHUB_TOKEN", global = true)]
     pub github_token: Option<String>,
 
+    /// File to read the GitHub API token from, so it does not end up in the shell history.
+    #[arg(long, env = "GITHUB_TOKEN_FILE", global = true, value_name = "path")]
+    pub github_token_file: Option<PathBuf>,
+
     /// Disable update warnings for suiup itself.
     #[arg(long, env = "SUIUP_DISABLE_UPDATE_WARNINGS", global = true)]
     pub disable_update_warnings: bool,
+
+    /// Skip the check for a newer suiup for this invocation.
+    #[arg(
+        long,
+        env = "SUIUP_NO_UPDATE_CHECK",
+        global = true,
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub no_update_check: bool,
+
+    /// Directory for installed binaries and suiup's state, instead of the `suiup` folder in the
+    /// data home.
+    #[arg(long, env = "SUIUP_DATA_DIR", global = true, value_name = "path")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Directory for downloaded release archives, instead of the `suiup` folder in the cache home.
+    #[arg(long, env = "SUIUP_CACHE_DIR", global = true, value_name = "path")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Directory for suiup's configuration files, instead of the `suiup` folder in the config home.
+    #[arg(long, env = "SUIUP_CONFIG_DIR", global = true, value_name = "path")]
+    pub config_dir: Option<PathBuf>,
+
+    /// How to print paths: `native`, or `posix` for Git Bash/WSL on Windows.
+    #[arg(long, value_enum, env = "SUIUP_PATH_STYLE", default_value_t = PathStyle::Native, global = true)]
+    pub path_style: PathStyle,
+
+    /// Use cached release lists right away, whatever their age, and refresh them in the background
+    /// for the next run. This invocation may see slightly stale releases.
+    #[arg(long, env = "SUIUP_OFFLINE_FIRST", global = true)]
+    pub offline_first: bool,
+
+    /// Log debug details, such as download URLs and why a cached archive was (not) reused.
+    #[arg(short, long, env = "SUIUP_VERBOSE", global = true)]
+    pub verbose: bool,
+
+    /// Print plain ASCII instead of emoji and other symbols (implied by `TERM=dumb`).
+    #[arg(long, env = "SUIUP_ASCII", global = true)]
+    pub ascii: bool,
+
+    /// Answer yes to every confirmation, as if `-y` was passed (also `assume_yes` in the config
+    /// file).
+    #[arg(
+        long,
+        env = "SUIUP_ASSUME_YES",
+        global = true,
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub assume_yes: bool,
+
+    /// Keep the current default version after an install instead of asking, even with `-y` or
+    /// `assume_yes`. Other confirmations, such as `confirm_mainnet`, are not affected.
+    #[arg(long, global = true)]
+    pub assume_no: bool,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
+    Alias(alias::Command),
     Default(default::Command),
     Doctor(doctor::Command),
     Install(install::Command),
     Remove(remove::Command),
     List(list::Command),
+    Run(run::Command),
 
     #[command(name = "self")]
     Self_(self_::Command),
@@ -53,41 +128,154 @@ pub enum Commands {
     Update(update::Command),
     Which(which::Command),
     Cleanup(cleanup::Command),
+    Cache(cache::Command),
+    Completion(completion::Command),
+    Prune(prune::Command),
     #[cfg(feature = "nix-patchelf")]
     Patch(patch::Command),
 }
 
 impl Command {
     pub async fn exec(&self) -> Result<()> {
-        // Check for updates before executing any command (except self update to avoid recursion)
-        if !matches!(self.command, Commands::Self_(_)) && !self.disable_update_warnings {
-            check_for_updates();
+        set_path_style(self.path_style);
+        set_ascii(self.ascii);
+        set_offline_first(self.offline_first);
+        set_dir_overrides(DirOverrides {
+            data_dir: self.data_dir.clone(),
+            cache_dir: self.cache_dir.clone(),
+            config_dir: self.config_dir.clone(),
+        });
+        if read_only_mode() && !self.is_read_only() {
+            bail!(
+                "suiup is in read-only mode (SUIUP_READONLY is set): binaries and settings cannot be changed"
+            );
+        }
+        initialize()?;
+        let config = Config::load()?;
+        set_binary_layout(config.binary_layout);
+        set_assumed_answers(self.assume_yes || config.assume_yes, self.assume_no);
+        let github_token = self.github_token()?;
+
+        // Check for updates before executing any command (except self update to avoid recursion,
+        // run and completion, which should only show the output of the binary or the script, and
+        // `which`, which scripts call and expect to return right away)
+        if !matches!(
+            self.command,
+            Commands::Self_(_) | Commands::Run(_) | Commands::Completion(_) | Commands::Which(_)
+        ) && !self.disable_update_warnings
+            && !self.no_update_check
+        {
+            check_for_updates(github_token.clone());
         }
 
         match &self.command {
+            Commands::Alias(cmd) => cmd.exec(),
             Commands::Default(cmd) => cmd.exec(),
-            Commands::Doctor(cmd) => cmd.exec(&self.github_token).await,
-            Commands::Install(cmd) => cmd.exec(&self.github_token).await,
-            Commands::Remove(cmd) => cmd.exec(&self.github_token).await,
-            Commands::List(cmd) => cmd.exec(&self.github_token).await,
-            Commands::Self_(cmd) => cmd.exec().await,
-            Commands::Show(cmd) => cmd.exec(),
+            Commands::Doctor(cmd) => cmd.exec(&github_token).await,
+            Commands::Install(cmd) => cmd.exec(&github_token).await,
+            Commands::Remove(cmd) => cmd.exec(&github_token).await,
+            Commands::List(cmd) => cmd.exec(&github_token).await,
+            Commands::Run(cmd) => cmd.exec().await,
+            Commands::Self_(cmd) => cmd.exec(&github_token).await,
+            Commands::Show(cmd) => cmd.exec(self.verbose, &github_token).await,
             Commands::Switch(cmd) => cmd.exec(),
-            Commands::Update(cmd) => cmd.exec(&self.github_token).await,
+            Commands::Update(cmd) => cmd.exec(&github_token).await,
             Commands::Which(cmd) => cmd.exec(),
-            Commands::Cleanup(cmd) => cmd.exec(&self.github_token).await,
+            Commands::Cleanup(cmd) => cmd.exec(&github_token).await,
+            Commands::Cache(cmd) => cmd.exec(&github_token).await,
+            Commands::Completion(cmd) => cmd.exec(),
+            Commands::Prune(cmd) => cmd.exec(),
             #[cfg(feature = "nix-patchelf")]
             Commands::Patch(cmd) => cmd.exec(),
         }
     }
+
+    /// Whether the command only reads the installed binaries and settings, so it is allowed in
+    /// read-only mode
+    fn is_read_only(&self) -> bool {
+        match &self.command {
+            Commands::Alias(cmd) => cmd.is_read_only(),
+            Commands::Default(cmd) => cmd.is_read_only(),
+            Commands::Doctor(cmd) => cmd.is_read_only(),
+            Commands::Install(cmd) => cmd.is_read_only(),
+            Commands::Self_(cmd) => cmd.is_read_only(),
+            Commands::Cleanup(cmd) => cmd.is_read_only(),
+            Commands::Prune(cmd) => cmd.is_read_only(),
+            Commands::List(_)
+            | Commands::Run(_)
+            | Commands::Show(_)
+            | Commands::Which(_)
+            | Commands::Completion(_) => true,
+            Commands::Remove(_)
+            | Commands::Switch(_)
+            | Commands::Update(_)
+            | Commands::Cache(_) => false,
+            #[cfg(feature = "nix-patchelf")]
+            Commands::Patch(_) => false,
+        }
+    }
+
+    /// Returns the GitHub token passed inline, read from `--github-token-file`, or from `GH_TOKEN`
+    fn github_token(&self) -> Result<Option<String>> {
+        let Some(path) = &self.github_token_file else {
+            return Ok(resolve_github_token(
+                self.github_token.clone(),
+                std::env::var("GH_TOKEN").ok(),
+            ));
+        };
+        // GITHUB_TOKEN is commonly exported in CI, so only an inline --github-token conflicts
+        if self.github_token.is_some() && std::env::var("GITHUB_TOKEN").ok() != self.github_token {
+            bail!("--github-token and --github-token-file cannot be used together");
+        }
+        let token = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read GitHub token file {}: {e}", path.display()))?;
+        let token = token.trim();
+        if token.is_empty() {
+            bail!("GitHub token file {} is empty", path.display());
+        }
+        Ok(Some(token.to_string()))
+    }
+}
+
+/// Picks the GitHub token: `--github-token` or `GITHUB_TOKEN` (which clap reads into `inline`),
+/// then `GH_TOKEN`. Empty values count as unset.
+fn resolve_github_token(inline: Option<String>, gh_token: Option<String>) -> Option<String> {
+    [inline, gh_token]
+        .into_iter()
+        .flatten()
+        .find(|token| !token.trim().is_empty())
+}
+
+/// Whether `SUIUP_READONLY` is set (to anything but `0`, `false` or nothing), e.g. by an admin so
+/// that the users of a shared install cannot change it by accident
+fn read_only_mode() -> bool {
+    std::env::var("SUIUP_READONLY")
+        .is_ok_and(|value| !matches!(value.trim().to_lowercase().as_str(), "" | "0" | "false"))
 }
 
 #[derive(Subcommand)]
 pub enum ComponentCommands {
     #[command(about = "Run diagnostic checks on the environment")]
-    Doctor,
+    Doctor {
+        /// Remove default entries whose binaries are missing on disk
+        #[arg(long)]
+        fix: bool,
+        /// Also check the free space where binaries and release archives are stored
+        #[arg(long)]
+        check_disk: bool,
+        /// Also check that GitHub accepts the configured token
+        #[arg(long)]
+        check_token: bool,
+    },
     #[command(about = "List available binaries to install")]
-    List,
+    List {
+        /// Print the binaries, their repositories and networks as JSON
+        #[arg(long)]
+        json: bool,
+        /// Print everything at once instead of through $PAGER
+        #[arg(long)]
+        no_pager: bool,
+    },
     #[command(about = "Add a binary")]
     Add {
         #[arg(
@@ -111,36 +299,99 @@ pub enum ComponentCommands {
         nightly: Option<String>,
         #[arg(short, long, help = "Accept defaults without prompting")]
         yes: bool,
+        #[arg(
+            long,
+            value_name = "name",
+            help = "Install the binary into the default bin folder under a custom name (e.g. 'sui-test'), next to the managed one"
+        )]
+        binary_name: Option<String>,
+        #[arg(
+            long,
+            value_name = "algo:hex",
+            conflicts_with = "nightly",
+            help = "Verify the downloaded file against this checksum (e.g. 'sha256:<hex>' or 'md5:<hex>')"
+        )]
+        checksum: Option<String>,
+        #[arg(
+            long,
+            value_name = "binaries",
+            value_delimiter = ',',
+            conflicts_with = "nightly",
+            help = "Also install these companion binaries from the same release archive (e.g. 'sui-tool,sui-faucet')"
+        )]
+        also: Vec<String>,
+        #[arg(
+            long,
+            value_name = "os-arch",
+            conflicts_with_all = ["nightly", "binary_name"],
+            help = "Download the binary for another platform (e.g. 'linux-x86_64') without running it or making it the default"
+        )]
+        target: Option<String>,
+        #[arg(long, help = "Do not make the installed binary the default one")]
+        no_default: bool,
+        #[arg(
+            long,
+            value_name = "path",
+            conflicts_with = "nightly",
+            help = "Write the resolved version (e.g. 'testnet-v1.40.1') to this file after installing"
+        )]
+        version_file: Option<PathBuf>,
     },
     #[command(
-        about = "Remove one. By default, the binary from each release will be removed. Use --version to specify which exact version to remove"
+        about = "Remove one or more binaries. By default, the binary from each release will be removed"
     )]
     Remove {
-        #[arg(value_enum)]
-        binary: BinaryName,
+        /// Binaries to remove
+        #[arg(value_enum, required_unless_present = "all", conflicts_with = "all")]
+        binaries: Vec<BinaryName>,
+        /// Remove every installed binary and clear the defaults
+        #[arg(long)]
+        all: bool,
     },
     #[command(about = "Cleanup cache files")]
+    #[command(group(ArgGroup::new("binaries_filter").args(["network", "component"]).multiple(true)))]
     Cleanup {
         /// Remove all cache files
         /// If not specified, only cache files older than `days` will be removed
         #[arg(long, conflicts_with = "days")]
         all: bool,
-        /// Days to keep files in cache (default: 30)
-        #[arg(long, short = 'd', default_value = "30")]
-        days: u32,
+        /// Days to keep files in cache (default: 30). With --network, only together with
+        /// --component
+        #[arg(long, short = 'd')]
+        days: Option<u32>,
+        /// Also remove the cached release lists and their ETags, so they are fetched again
+        /// (implied by --all)
+        #[arg(long)]
+        caches: bool,
+        /// Keep only the N newest archives per binary and network, regardless of age
+        #[arg(long, conflicts_with_all = ["all", "days"])]
+        keep: Option<usize>,
+        /// Network (or nightly branch) whose extracted binaries and archives to remove, with --binaries
+        #[arg(long, requires = "binaries", conflicts_with_all = ["all", "keep"])]
+        network: Option<String>,
+        /// Only remove extracted binaries of this component older than --days, keeping its
+        /// default version, with --binaries
+        #[arg(long, value_enum, requires = "binaries", conflicts_with_all = ["all", "keep"])]
+        component: Option<BinaryName>,
+        /// Remove extracted binaries of --network and/or --component, not only release archives
+        #[arg(long, requires = "binaries_filter")]
+        binaries: bool,
         /// Show what would be removed without actually removing anything
         #[arg(long, short = 'n')]
         dry_run: bool,
+        /// Only print the cache sizes and the totals, instead of a line per file
+        #[arg(long)]
+        summary: bool,
     },
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq, ValueEnum)]
 #[value(rename_all = "lowercase")]
 pub enum BinaryName {
-    #[value(name = "mvr")]
-    Mvr,
     #[value(name = "sui")]
     Sui,
+    #[value(name = "mvr")]
+    Mvr,
     #[value(name = "walrus")]
     Walrus,
     #[value(name = "site-builder")]
@@ -164,7 +415,33 @@ impl BinaryName {
         }
     }
 
-    pub fn to_str(&self) -> &str {
+    /// The repository the binary is released from
+    pub fn repo(&self) -> Repo {
+        match self {
+            BinaryName::Mvr => Repo::Mvr,
+            BinaryName::Sui => Repo::Sui,
+            BinaryName::Walrus => Repo::Walrus,
+            BinaryName::WalrusSites => Repo::WalrusSites,
+        }
+    }
+
+    /// Whether the binary is versioned on its own (`mvr@0.0.8`) rather than per network
+    /// (`sui@testnet-1.40.1`)
+    pub fn is_standalone(&self) -> bool {
+        matches!(self, BinaryName::Mvr)
+    }
+
+    /// How the binary is released, as shown by `suiup list`
+    pub fn release_type(&self) -> &'static str {
+        if self.is_standalone() {
+            "standalone"
+        } else {
+            "network-scoped"
+        }
+    }
+
+    /// The name of the binary, which is also how it is spelled in specs and on the command line
+    pub fn to_str(&self) -> &'static str {
         match self {
             BinaryName::Mvr => "mvr",
             BinaryName::Sui => "sui",
@@ -176,12 +453,7 @@ impl BinaryName {
 
 impl std::fmt::Display for BinaryName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            BinaryName::Mvr => write!(f, "mvr"),
-            BinaryName::Sui => write!(f, "sui"),
-            BinaryName::Walrus => write!(f, "walrus"),
-            BinaryName::WalrusSites => write!(f, "site-builder"),
-        }
+        f.write_str(self.to_str())
     }
 }
 
@@ -189,60 +461,115 @@ impl std::str::FromStr for BinaryName {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "sui" => Ok(BinaryName::Sui),
-            "mvr" => Ok(BinaryName::Mvr),
-            "walrus" => Ok(BinaryName::Walrus),
-            "site-builder" => Ok(BinaryName::WalrusSites),
-            _ => Err(format!("Unknown binary: {}", s)),
-        }
+        let name = s.to_lowercase();
+        BinaryName::value_variants()
+            .iter()
+            .find(|binary| binary.to_str() == name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown binary: {}", s))
+    }
+}
+
+/// Parses a `binary@spec` spec, or an alias from `config` standing for one. Specs without a
+/// network use the network remembered in `config` for the binary.
+pub fn parse_component_with_version(
+    s: &str,
+    config: &Config,
+) -> Result<CommandMetadata, anyhow::Error> {
+    let s = resolve_alias(s, config);
+    let (binary, spec) = split_component_spec(&s)?;
+    let component = BinaryName::from_str(binary, true).map_err(|_| {
+        anyhow!(
+            "Invalid binary name: {}. Use `suiup list` to find available binaries to install.",
+            binary
+        )
+    })?;
+    let default_network = config.default_network(&component.to_string());
+    let (network, version) = parse_version_spec(spec.map(str::to_string), default_network)?;
+    Ok(CommandMetadata {
+        name: component,
+        network,
+        version,
+    })
+}
+
+/// Returns `spec` unchanged if it names a version or network, otherwise the spec pinned for the
+/// binary by a `.<binary>-version` file (e.g. `sui` becomes `sui@testnet-1.40.1`). Invalid specs
+/// are returned unchanged, so they are reported by the command itself.
+pub fn resolve_pinned_spec(spec: &str, config: &Config) -> Result<String> {
+    let spec = resolve_alias(spec, config);
+    let Ok((binary, None)) = split_component_spec(&spec) else {
+        return Ok(spec);
+    };
+    Ok(match read_version_file(binary)? {
+        Some(version) => format!("{binary}@{version}"),
+        None => spec,
+    })
+}
+
+/// Returns the spec an alias from `suiup alias set` stands for (e.g. `my-stable` becomes
+/// `sui@testnet-1.39.3`), or `spec` unchanged when it is not an alias
+pub fn resolve_alias(spec: &str, config: &Config) -> String {
+    match config.aliases.get(spec.trim()) {
+        Some(aliased) => aliased.clone(),
+        None => spec.to_string(),
     }
 }
 
-pub fn parse_component_with_version(s: &str) -> Result<CommandMetadata, anyhow::Error> {
-    let split_char = if s.contains("@") {
-        "@"
-    } else if s.contains("==") {
-        "=="
-    } else if s.contains("=") {
-        "="
-    } else {
-        // TODO this is a hack because we don't have a better way to split
-        " "
+/// Splits a `binary@version` spec (also accepting `==` and `=` as separator) once on the first
+/// separator, and trims whitespace around both parts. A single space is accepted as separator
+/// when no other separator is present, e.g. `mvr 0.0.8`.
+fn split_component_spec(s: &str) -> Result<(&str, Option<&str>)> {
+    const SEPARATORS: [&str; 3] = ["@", "==", "="];
+
+    let s = s.trim();
+    // the earliest separator wins, `==` is listed before `=` so it wins at the same position
+    let separator = SEPARATORS
+        .iter()
+        .filter_map(|sep| s.find(sep).map(|idx| (idx, *sep)))
+        .min_by_key(|(idx, _)| *idx);
+
+    let (binary, spec) = match separator {
+        Some((idx, sep)) => (&s[..idx], Some(&s[idx + sep.len()..])),
+        None => match s.split_once(char::is_whitespace) {
+            Some((binary, spec)) => (binary, Some(spec)),
+            None => (s, None),
+        },
     };
 
-    let parts: Vec<&str> = s.split(split_char).collect();
-
-    match parts.len() {
-        1 => {
-            let component = BinaryName::from_str(parts[0], true)
-                .map_err(|_| anyhow!("Invalid binary name: {}. Use `suiup list` to find available binaries to install.", parts[0]))?;
-            let (network, version) = parse_version_spec(None)?;
-            let component_metadata = CommandMetadata {
-                name: component,
-                network,
-                version,
-            };
-            Ok(component_metadata)
+    let binary = binary.trim();
+    if binary.is_empty() {
+        bail!("Invalid format: binary name cannot be empty. Use 'binary' or 'binary@version'");
+    }
+
+    let spec = spec.map(str::trim);
+    if let Some(spec) = spec {
+        if spec.is_empty() {
+            bail!("Invalid format: version cannot be empty for {binary}. Use 'binary' or 'binary@version'");
         }
-        2 => {
-            let component = BinaryName::from_str(parts[0], true)
-                .map_err(|_| anyhow!("Invalid binary name: {}. Use `suiup list` to find available binaries to install.", parts[0]))?;
-            let (network, version) = parse_version_spec(Some(parts[1].to_string()))?;
-            let component_metadata = CommandMetadata {
-                name: component,
-                network,
-                version,
-            };
-            Ok(component_metadata)
+        if spec.contains(|c: char| c == '@' || c == '=' || c.is_whitespace()) {
+            bail!("Invalid format: {s}. Use 'binary' or 'binary@version'");
         }
-        _ => bail!("Invalid format. Use 'binary' or 'binary version'".to_string()),
     }
+
+    Ok((binary, spec))
+}
+
+/// Returns the network a `binary@network[-version]` spec names, if any
+pub fn spec_network(s: &str) -> Option<String> {
+    let (_, spec) = split_component_spec(s).ok()?;
+    let network = spec?.split('-').next()?;
+    NETWORKS.contains(&network).then(|| network.to_string())
 }
 
-pub fn parse_version_spec(spec: Option<String>) -> Result<(String, Option<String>)> {
+/// Splits a version spec into network and version. Specs without a network (`None` or a bare
+/// version) use `default_network`.
+pub fn parse_version_spec(
+    spec: Option<String>,
+    default_network: &str,
+) -> Result<(String, Option<String>)> {
     match spec {
-        None => Ok(("testnet".to_string(), None)),
+        None => Ok((default_network.to_string(), None)),
         Some(spec) => {
             if spec.starts_with("testnet-")
                 || spec.starts_with("devnet-")
@@ -253,47 +580,110 @@ pub fn parse_version_spec(spec: Option<String>) -> Result<(String, Option<String
             } else if spec == "testnet" || spec == "devnet" || spec == "mainnet" {
                 Ok((spec, None))
             } else {
-                // Assume it's a version for testnet
-                Ok(("testnet".to_string(), Some(spec)))
+                // Assume it's a version for the default network
+                Ok((default_network.to_string(), Some(spec)))
             }
         }
     }
 }
 
-pub fn print_table(binaries: &Vec<BinaryVersion>) {
-    let mut binaries_vec = binaries.clone();
+/// Prints default binaries as a table, see [`format_table`]
+pub fn print_table(binaries: &[BinaryVersion]) {
+    println!("{}", format_table(binaries, false, true));
+}
+
+/// Renders installed binaries as a table, sorted by binary name. With `released`, the publish
+/// date of the release each binary was installed from is added. With `defaults`, the binaries are
+/// default versions, and the ones whose file no longer exists are marked "(missing)" so they are
+/// not presented as usable.
+pub fn format_table(binaries: &[BinaryVersion], released: bool, defaults: bool) -> String {
+    let mut binaries_vec = binaries.to_vec();
     // sort by Binary column
     binaries_vec.sort_by_key(|b| b.binary_name.clone());
+    let mut header = vec!["Binary", "Release/Branch", "Version", "Debug"];
+    if released {
+        header.push("Released");
+    }
     let mut table = Table::new();
-    table
-        .load_preset(TABLE_FORMAT)
-        .set_header(vec!["Binary", "Release/Branch", "Version", "Debug"])
-        .add_rows(
-            binaries_vec
-                .into_iter()
-                .map(|binary| {
-                    vec![
-                        binary.binary_name,
-                        binary.network_release,
-                        binary.version,
-                        if binary.debug {
-                            "Yes".to_string()
-                        } else {
-                            "No".to_string()
-                        },
-                    ]
-                })
-                .collect::<Vec<Vec<String>>>(),
-        );
-    println!("{table}");
+    table.load_preset(TABLE_FORMAT).set_header(header).add_rows(
+        binaries_vec
+            .into_iter()
+            .map(|binary| {
+                let mut version = binary.display_version();
+                if defaults && !default_binary_exists(&binary) {
+                    version.push_str(" (missing)");
+                }
+                let mut row = vec![
+                    binary.binary_name,
+                    binary.network_release,
+                    version,
+                    if binary.debug {
+                        "Yes".to_string()
+                    } else {
+                        "No".to_string()
+                    },
+                ];
+                if released {
+                    row.push(binary.released_at.unwrap_or_else(|| "-".to_string()));
+                }
+                row
+            })
+            .collect::<Vec<Vec<String>>>(),
+    );
+    table.to_string()
 }
 
 #[cfg(test)]
 mod tests {
-    use clap::CommandFactory;
+    use clap::{CommandFactory, ValueEnum};
+
+    use super::BinaryName;
+    use crate::handlers::available_components;
+    use crate::types::Config;
 
     #[test]
     fn verify_command() {
         super::Command::command().debug_assert();
     }
+
+    #[test]
+    fn test_resolve_github_token() {
+        let token = |t: &str| Some(t.to_string());
+        assert_eq!(
+            super::resolve_github_token(token("inline"), token("gh")),
+            token("inline")
+        );
+        assert_eq!(super::resolve_github_token(None, token("gh")), token("gh"));
+        assert_eq!(
+            super::resolve_github_token(token(""), token("gh")),
+            token("gh")
+        );
+        assert_eq!(super::resolve_github_token(None, token(" ")), None);
+        assert_eq!(super::resolve_github_token(None, None), None);
+    }
+
+    #[test]
+    fn test_binary_names_agree() {
+        for binary in BinaryName::value_variants() {
+            let name = binary.to_str();
+            assert_eq!(binary.to_string(), name);
+            assert_eq!(name.parse::<BinaryName>().as_ref(), Ok(binary));
+            assert_eq!(
+                name.to_uppercase().parse::<BinaryName>().as_ref(),
+                Ok(binary)
+            );
+            assert_eq!(binary.to_possible_value().unwrap().get_name(), name);
+            assert_eq!(
+                super::parse_component_with_version(name, &Config::default())
+                    .unwrap()
+                    .name,
+                *binary
+            );
+        }
+        let names: Vec<_> = BinaryName::value_variants()
+            .iter()
+            .map(BinaryName::to_str)
+            .collect();
+        assert_eq!(available_components(), names);
+    }
 }
@@ -0,0 +1,26 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::cleanup::handle_prune;
+
+/// Remove every installed binary except the default ones and those pinned by a
+/// `.<binary>-version` file, together with the release archives they no longer need.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Show what would be removed without actually removing anything
+    #[arg(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl Command {
+    pub fn is_read_only(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn exec(&self) -> Result<()> {
+        handle_prune(self.dry_run)
+    }
+}
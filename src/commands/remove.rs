@@ -11,15 +11,21 @@ use super::{BinaryName, ComponentCommands};
 /// Remove one or more binaries.
 #[derive(Args, Debug)]
 pub struct Command {
-    #[arg(value_enum)]
-    binary: BinaryName,
+    /// Binaries to remove (e.g. 'sui walrus mvr')
+    #[arg(value_enum, required_unless_present = "all", conflicts_with = "all")]
+    binaries: Vec<BinaryName>,
+
+    /// Remove every installed binary and clear the defaults
+    #[arg(long)]
+    all: bool,
 }
 
 impl Command {
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
         handle_cmd(
             ComponentCommands::Remove {
-                binary: self.binary.to_owned(),
+                binaries: self.binaries.to_owned(),
+                all: self.all,
             },
             github_token.to_owned(),
         )
@@ -0,0 +1,27 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::commands::resolve_alias;
+use crate::handlers::run::handle_run;
+use crate::types::Config;
+
+/// Run an installed binary, forwarding signals and its exit code.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Binary to run, either the default one (e.g. 'sui'), the latest installed version for a
+    /// network/release (e.g. 'sui@testnet') or an alias from `suiup alias set`
+    binary: String,
+
+    /// Arguments passed to the binary (e.g. `suiup run sui -- client active-address`)
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+impl Command {
+    pub async fn exec(&self) -> Result<()> {
+        handle_run(&resolve_alias(&self.binary, &Config::load()?), &self.args).await
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::self_;
+
+/// Check that GitHub accepts the configured token and show its remaining request budget.
+#[derive(Args, Debug)]
+pub struct Command;
+
+impl Command {
+    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+        self_::handle_check_token(github_token.as_deref()).await
+    }
+}
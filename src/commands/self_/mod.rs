@@ -1,8 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod check_token;
+mod rollback;
 mod uninstall;
 mod update;
+mod version;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
@@ -17,15 +20,30 @@ pub struct Command {
 #[derive(Subcommand, Debug)]
 enum Commands {
     Update(update::Command),
+    Rollback(rollback::Command),
     Uninstall(uninstall::Command),
+    Version(version::Command),
+    CheckToken(check_token::Command),
 }
 
 impl Command {
+    /// Whether the command leaves the suiup binary as it is
+    pub fn is_read_only(&self) -> bool {
+        match &self.command {
+            Commands::Update(cmd) => cmd.is_read_only(),
+            Commands::Version(_) | Commands::CheckToken(_) => true,
+            Commands::Rollback(_) | Commands::Uninstall(_) => false,
+        }
+    }
+
     /// Handles the self commands
-    pub async fn exec(&self) -> Result<()> {
+    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
         match &self.command {
-            Commands::Update(cmd) => cmd.exec().await,
+            Commands::Update(cmd) => cmd.exec(github_token).await,
+            Commands::Rollback(cmd) => cmd.exec(),
             Commands::Uninstall(cmd) => cmd.exec(),
+            Commands::Version(cmd) => cmd.exec(),
+            Commands::CheckToken(cmd) => cmd.exec(github_token).await,
         }
     }
 }
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::self_;
+
+/// Restore the suiup version that was replaced by the last self update.
+#[derive(Args, Debug)]
+pub struct Command;
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        self_::handle_rollback()
+    }
+}
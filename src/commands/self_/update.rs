@@ -8,10 +8,27 @@ use crate::handlers::self_;
 
 /// Update suiup itself.
 #[derive(Args, Debug)]
-pub struct Command;
+pub struct Command {
+    /// Only print the version and asset that would be installed, without downloading anything
+    #[arg(long, short = 'n')]
+    dry_run: bool,
+
+    /// Only print the running and the latest version. Exits with 0 when suiup is up to date and
+    /// 100 when an update is available.
+    #[arg(long, conflicts_with = "dry_run")]
+    check: bool,
+}
 
 impl Command {
-    pub async fn exec(&self) -> Result<()> {
-        self_::handle_update().await
+    /// Whether the command only reports the available update
+    pub fn is_read_only(&self) -> bool {
+        self.check || self.dry_run
+    }
+
+    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+        if self.check {
+            return self_::handle_check_update(github_token.to_owned()).await;
+        }
+        self_::handle_update(github_token.to_owned(), self.dry_run).await
     }
 }
@@ -0,0 +1,21 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::self_;
+
+/// Show the suiup version together with build metadata.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Print the build metadata as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        self_::handle_version(self.json)
+    }
+}
@@ -4,7 +4,7 @@
 use anyhow::Result;
 use clap::Args;
 
-use crate::handlers::show::handle_show;
+use crate::handlers::show::{handle_show, handle_show_outdated};
 
 /// Show installed and active binaries.
 #[derive(Args, Debug)]
@@ -12,10 +12,42 @@ pub struct Command {
     /// Show only default binaries
     #[arg(long)]
     default: bool,
+
+    /// Show installed binaries as a tree of binary, network and versions, with the default
+    /// marked by `*`
+    #[arg(long, conflicts_with = "default")]
+    tree: bool,
+
+    /// Print everything at once instead of through $PAGER when the output does not fit on the
+    /// screen
+    #[arg(long)]
+    no_pager: bool,
+
+    /// Only list the installed binaries that have a newer release, with the installed and the
+    /// latest version. Fails if there is any, e.g. to flag drift in CI.
+    #[arg(long, conflicts_with_all = ["default", "tree"])]
+    outdated: bool,
+
+    /// Only show debug builds, e.g. to find the large ones to prune
+    #[arg(long, conflicts_with_all = ["no_debug", "outdated"])]
+    debug_only: bool,
+
+    /// Leave debug builds out
+    #[arg(long, conflicts_with = "outdated")]
+    no_debug: bool,
 }
 
 impl Command {
-    pub fn exec(&self) -> Result<()> {
-        handle_show(self.default)
+    /// `verbose` is the global `--verbose` flag, which adds the release publish dates
+    pub async fn exec(&self, verbose: bool, github_token: &Option<String>) -> Result<()> {
+        if self.outdated {
+            return handle_show_outdated(github_token.clone()).await;
+        }
+        let debug = match (self.debug_only, self.no_debug) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
+        handle_show(self.default, self.tree, debug, self.no_pager, verbose)
     }
 }
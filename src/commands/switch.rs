@@ -4,19 +4,51 @@
 use anyhow::Result;
 use clap::Args;
 
-use crate::handlers::switch::handle_switch;
+use crate::commands::resolve_pinned_spec;
+use crate::handlers::{
+    confirm_mainnet,
+    switch::{handle_switch, parse_binary_spec},
+};
+use crate::types::Config;
 
 /// Switch to a different version of an installed binary.
 #[derive(Args, Debug)]
 pub struct Command {
-    /// Binary and network/release to switch to
+    /// One or more binaries and network/release to switch to
     /// e.g. 'sui@testnet', 'mvr@main', 'walrus@testnet'
-    /// This will use the latest installed version for that network/release
-    binary_spec: String,
+    /// This will use the latest installed version for that network/release.
+    /// A bare binary name (e.g. 'sui') uses the spec from a `.sui-version` file in the current
+    /// directory or its parents
+    #[arg(required = true)]
+    binary_specs: Vec<String>,
+
+    /// Switch to the latest installed version for the network/release (default behavior)
+    #[arg(long, conflicts_with = "version")]
+    latest: bool,
+
+    /// Switch to a specific installed version instead of the latest one (e.g. 'v1.39.3')
+    #[arg(long)]
+    version: Option<String>,
+
+    /// Skip the mainnet confirmation (when `confirm_mainnet` is enabled in the config)
+    #[arg(short, long)]
+    yes: bool,
 }
 
 impl Command {
     pub fn exec(&self) -> Result<()> {
-        handle_switch(&self.binary_spec)
+        let config = Config::load()?;
+        let binary_specs = self
+            .binary_specs
+            .iter()
+            .map(|spec| resolve_pinned_spec(spec, &config))
+            .collect::<Result<Vec<_>>>()?;
+        for spec in &binary_specs {
+            // invalid specs are reported by the switch itself
+            if let Ok((_, network, _)) = parse_binary_spec(spec) {
+                confirm_mainnet(&network, "switch to a mainnet binary", self.yes)?;
+            }
+        }
+        handle_switch(&binary_specs, self.version.as_deref())
     }
 }
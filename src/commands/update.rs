@@ -13,6 +13,11 @@ pub struct Command {
     /// binary version. For updating a specific release, use the `sui@testnet` form.
     name: String,
 
+    /// Only update the install from this network release (e.g. 'mainnet'), leaving the other
+    /// networks untouched
+    #[arg(long, value_name = "network")]
+    network: Option<String>,
+
     /// Accept defaults without prompting
     #[arg(short, long)]
     yes: bool,
@@ -22,6 +27,7 @@ impl Command {
     pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
         handle_update(
             self.name.to_owned(),
+            self.network.to_owned(),
             self.yes.to_owned(),
             github_token.to_owned(),
         )
@@ -4,14 +4,19 @@
 use anyhow::Result;
 use clap::Args;
 
-use crate::handlers::which::handle_which;
+use crate::handlers::which::{handle_which, WhichFormat};
 
 /// Show the path where default binaries are installed.
 #[derive(Args, Debug)]
-pub struct Command;
+pub struct Command {
+    /// `path` prints the folder, `env` prints a `SUI_BIN=<path>` line per default binary and
+    /// per binary installed with `--binary-name`
+    #[arg(long, value_enum, default_value_t = WhichFormat::Path)]
+    format: WhichFormat,
+}
 
 impl Command {
     pub fn exec(&self) -> Result<()> {
-        handle_which()
+        handle_which(self.format)
     }
 }
@@ -1,38 +1,55 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::handlers::disk::{install_dirs_space, LOW_DISK_SPACE};
+use crate::handlers::release::{check_github_token, RateLimit, TokenStatus};
+use crate::handlers::version::{reported_version, version_matches, VERSION_TIMEOUT};
+use crate::handlers::{
+    available_components, default_binary_exists, remove_default_version_entries,
+};
 use crate::paths::{
     default_file_path, get_default_bin_dir, get_suiup_data_dir, installed_binaries_file,
 };
-use crate::types::InstalledBinaries;
+use crate::symbols::symbols;
+use crate::types::{Binaries, InstalledBinaries, Version};
 use anyhow::Result;
 use colored::Colorize;
+use indicatif::HumanBytes;
+use std::collections::BTreeMap;
 use std::env;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-pub async fn run_doctor_checks() -> Result<()> {
+pub async fn run_doctor_checks(
+    fix: bool,
+    check_disk: bool,
+    check_token: bool,
+    github_token: Option<&str>,
+) -> Result<()> {
     println!("\n{}", "Suiup Environment Doctor".bold());
-    println!("{}", "------------------------");
+    println!("------------------------");
 
     let mut warnings = 0;
     let mut errors = 0;
 
+    let symbols = symbols();
     let mut check = |message: &str, result: Result<String, String>| match result {
-        Ok(info) if info.is_empty() => println!("[{}] {}", "✓".green(), message),
-        Ok(info) => println!("[{}] {} {}", "✓".green(), message, info.dimmed()),
+        Ok(info) if info.is_empty() => println!("[{}] {}", symbols.ok.green(), message),
+        Ok(info) => println!("[{}] {} {}", symbols.ok.green(), message, info.dimmed()),
         Err(e) => {
             if e.starts_with("WARN:") {
                 warnings += 1;
                 println!(
                     "[{}] {}",
-                    "!".yellow(),
+                    symbols.warn.yellow(),
                     e.strip_prefix("WARN:").unwrap_or(&e).trim()
                 );
             } else {
                 errors += 1;
                 println!(
                     "[{}] {}",
-                    "✗".red(),
+                    symbols.fail.red(),
                     e.strip_prefix("ERROR:").unwrap_or(&e).trim()
                 );
             }
@@ -42,8 +59,16 @@ pub async fn run_doctor_checks() -> Result<()> {
     check("suiup data directory exists", check_suiup_data_dir());
     check_path_variables(&mut check);
     check_config_files(&mut check);
+    check_default_binaries(&mut check, fix);
+    check_default_binaries_run(&mut check);
     check_dependencies(&mut check);
-    check_network_connectivity(&mut check).await;
+    if check_disk {
+        check_disk_space(&mut check);
+    }
+    check_network_connectivity(&mut check, github_token).await;
+    if check_token {
+        self::check_token(&mut check, github_token).await;
+    }
 
     println!("\n{}", "Checkup complete.".bold());
     if errors > 0 {
@@ -60,6 +85,29 @@ pub async fn run_doctor_checks() -> Result<()> {
     Ok(())
 }
 
+fn check_disk_space(check: &mut impl FnMut(&str, Result<String, String>)) {
+    for (label, path, free) in install_dirs_space() {
+        check(
+            &format!("Free space for {label}"),
+            disk_space_status(&path, free),
+        );
+    }
+}
+
+/// Result of the free space check of `path`, a warning below [`LOW_DISK_SPACE`]
+fn disk_space_status(path: &Path, free: Option<u64>) -> Result<String, String> {
+    match free {
+        None => Ok("unknown on this platform".to_string()),
+        Some(free) if free < LOW_DISK_SPACE => Err(format!(
+            "WARN: only {} free at {}, installs need about {}",
+            HumanBytes(free),
+            path.display(),
+            HumanBytes(LOW_DISK_SPACE)
+        )),
+        Some(free) => Ok(format!("{} at {}", HumanBytes(free), path.display())),
+    }
+}
+
 fn check_suiup_data_dir() -> Result<String, String> {
     let path = get_suiup_data_dir();
     if path.exists() && path.is_dir() {
@@ -93,6 +141,20 @@ fn check_path_variables(check: &mut impl FnMut(&str, Result<String, String>)) {
             } else {
                 check("Default binary directory in PATH", Ok("".to_string()));
 
+                let conflicts = find_conflicting_binaries(&paths, &default_bin_dir);
+                if conflicts.is_empty() {
+                    check("PATH conflicts", Ok("none found".to_string()));
+                }
+                for (name, path) in conflicts {
+                    check(
+                        "PATH conflicts",
+                        Err(format!(
+                            "WARN: found conflicting '{name}' at {} (shadows suiup's)",
+                            path.display()
+                        )),
+                    );
+                }
+
                 // Check PATH order
                 let cargo_bin_dir = dirs::home_dir().map(|p| p.join(".cargo/bin"));
                 if let Some(cargo_bin) = cargo_bin_dir {
@@ -119,6 +181,52 @@ fn check_path_variables(check: &mut impl FnMut(&str, Result<String, String>)) {
     }
 }
 
+/// Finds executables named like the binaries managed by suiup in the PATH entries that come before
+/// the default bin dir, and which therefore shadow the binaries installed there.
+fn find_conflicting_binaries(paths: &[PathBuf], default_bin_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Some(default_pos) = paths.iter().position(|p| p == default_bin_dir) else {
+        return vec![];
+    };
+
+    let mut conflicts = vec![];
+    for name in available_components() {
+        if !is_executable(&default_bin_dir.join(exe_name(name))) {
+            continue;
+        }
+        if let Some(path) = paths[..default_pos]
+            .iter()
+            .map(|dir| dir.join(exe_name(name)))
+            .find(|path| is_executable(path))
+        {
+            conflicts.push((name.to_string(), path));
+        }
+    }
+    conflicts
+}
+
+fn exe_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
 fn check_config_files(check: &mut impl FnMut(&str, Result<String, String>)) {
     let installed_path = installed_binaries_file();
     match installed_path {
@@ -179,6 +287,104 @@ fn check_config_files(check: &mut impl FnMut(&str, Result<String, String>)) {
     }
 }
 
+/// Checks that every default binary still exists on disk. With `fix`, stale entries are removed
+/// from the default version file.
+fn check_default_binaries(check: &mut impl FnMut(&str, Result<String, String>), fix: bool) {
+    let Ok(path) = default_file_path() else {
+        return;
+    };
+    let Some(default) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<BTreeMap<String, (String, Version, bool)>>(&c).ok())
+    else {
+        // reported by the config file check
+        return;
+    };
+
+    let missing: Vec<_> = Binaries::from(default)
+        .binaries
+        .into_iter()
+        .filter(|b| !default_binary_exists(b))
+        .collect();
+    if missing.is_empty() {
+        check("Default binaries", Ok("are installed".to_string()));
+        return;
+    }
+
+    let names: Vec<String> = missing.iter().map(|b| b.binary_name.clone()).collect();
+    let listed = missing
+        .iter()
+        .map(|b| format!("{} ({} {})", b.binary_name, b.network_release, b.version))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if !fix {
+        check(
+            "Default binaries",
+            Err(format!(
+                "WARN: Default binaries missing on disk: {listed}. Run `suiup doctor --fix` to remove the stale entries."
+            )),
+        );
+        return;
+    }
+
+    match remove_default_version_entries(&names) {
+        Ok(()) => check(
+            "Default binaries",
+            Ok(format!("removed stale entries: {listed}")),
+        ),
+        Err(e) => check(
+            "Default binaries",
+            Err(format!("ERROR: Failed to remove stale entries: {e}")),
+        ),
+    }
+}
+
+/// Runs every default binary with `--version`, so binaries that crash or were built for another
+/// architecture are reported before they are used
+fn check_default_binaries_run(check: &mut impl FnMut(&str, Result<String, String>)) {
+    let Some(default) = default_file_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|c| serde_json::from_str::<BTreeMap<String, (String, Version, bool)>>(&c).ok())
+    else {
+        // reported by the config file check
+        return;
+    };
+
+    let default_bin_dir = get_default_bin_dir();
+    for binary in Binaries::from(default).binaries {
+        // missing binaries are reported by the default binaries check
+        let path = default_bin_dir.join(exe_name(&binary.binary_name));
+        if !default_binary_exists(&binary) || !path.is_file() {
+            continue;
+        }
+        let mut cmd = Command::new(&path);
+        cmd.arg("--version");
+        check(
+            &format!("{} runs", binary.binary_name),
+            verify_binary_version(cmd, &binary.version, VERSION_TIMEOUT),
+        );
+    }
+}
+
+/// Runs `cmd` and checks that it exits successfully within `timeout` and prints `expected`
+/// (nightly builds only need to print something)
+fn verify_binary_version(
+    cmd: Command,
+    expected: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let reported = reported_version(cmd, timeout).map_err(|e| format!("ERROR: {e}"))?;
+    if !version_matches(&reported, expected) {
+        return Err(format!(
+            "WARN: reports '{reported}', but version {} is the default",
+            expected.trim_start_matches('v')
+        ));
+    }
+    Ok(reported)
+}
+
 fn check_dependencies(check: &mut impl FnMut(&str, Result<String, String>)) {
     // Check for rustc
     match Command::new("rustc").arg("--version").output() {
@@ -217,17 +423,47 @@ fn check_dependencies(check: &mut impl FnMut(&str, Result<String, String>)) {
     }
 }
 
-async fn check_network_connectivity(check: &mut impl FnMut(&str, Result<String, String>)) {
+async fn check_token(
+    check: &mut impl FnMut(&str, Result<String, String>),
+    github_token: Option<&str>,
+) {
+    let status = match check_github_token(github_token).await {
+        Ok(status) => token_status(status),
+        Err(e) => Err(format!("WARN: could not check the token: {e}")),
+    };
+    check("GitHub token", status);
+}
+
+/// Result of the token check: a missing token only limits the request budget, while a rejected
+/// one makes every request fail
+fn token_status(status: TokenStatus) -> Result<String, String> {
+    match status {
+        TokenStatus::Invalid => Err(format!("ERROR: GitHub token {status}")),
+        _ => Ok(status.to_string()),
+    }
+}
+
+async fn check_network_connectivity(
+    check: &mut impl FnMut(&str, Result<String, String>),
+    github_token: Option<&str>,
+) {
     let client = reqwest::Client::new();
 
-    match client
+    let mut request = client
         .get("https://api.github.com")
-        .header("User-Agent", "suiup")
-        .send()
-        .await
-    {
+        .header("User-Agent", "suiup");
+    if let Some(token) = github_token {
+        request = request.header("Authorization", format!("token {token}"));
+    }
+    match request.send().await {
         Ok(resp) if resp.status().is_success() => {
-            check("GitHub API connectivity", Ok("".to_string()))
+            check("GitHub API connectivity", Ok("".to_string()));
+            if let Some(rate_limit) = RateLimit::from_headers(resp.headers()) {
+                check(
+                    "GitHub API rate limit",
+                    rate_limit_status(rate_limit, github_token.is_some()),
+                );
+            }
         }
         _ => check(
             "GitHub API connectivity",
@@ -236,12 +472,154 @@ async fn check_network_connectivity(check: &mut impl FnMut(&str, Result<String,
     }
 }
 
+/// Describes the remaining GitHub API budget, warning when it is used up
+fn rate_limit_status(rate_limit: RateLimit, has_token: bool) -> Result<String, String> {
+    let token = if has_token {
+        "token configured"
+    } else {
+        "no token configured"
+    };
+    if rate_limit.remaining > 0 {
+        Ok(format!("{rate_limit}, {token}"))
+    } else if has_token {
+        Err(format!("WARN: GitHub API budget used up ({rate_limit})"))
+    } else {
+        Err(format!(
+            "WARN: GitHub API budget used up ({rate_limit}). Set GITHUB_TOKEN for a higher limit"
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_rate_limit_status() {
+        let rate_limit = RateLimit {
+            remaining: 47,
+            limit: 60,
+        };
+        assert_eq!(
+            rate_limit_status(rate_limit, false),
+            Ok("47/60 requests remaining, no token configured".to_string())
+        );
+
+        let rate_limit = RateLimit {
+            remaining: 0,
+            limit: 60,
+        };
+        let status = rate_limit_status(rate_limit, false).unwrap_err();
+        assert!(status.starts_with("WARN:") && status.contains("GITHUB_TOKEN"));
+        assert!(!rate_limit_status(rate_limit, true)
+            .unwrap_err()
+            .contains("GITHUB_TOKEN"));
+    }
+
+    #[test]
+    fn test_token_status() {
+        assert!(token_status(TokenStatus::Missing(None)).is_ok());
+        assert!(token_status(TokenStatus::Valid(None)).is_ok());
+        assert!(token_status(TokenStatus::Invalid)
+            .unwrap_err()
+            .starts_with("ERROR:"));
+    }
+
+    #[test]
+    fn test_disk_space_status() {
+        let path = Path::new("/data/suiup/binaries");
+        assert_eq!(
+            disk_space_status(path, Some(20_000_000_000)),
+            Ok("18.63 GiB at /data/suiup/binaries".to_string())
+        );
+        let status = disk_space_status(path, Some(300_000_000)).unwrap_err();
+        assert_eq!(
+            status,
+            "WARN: only 286.10 MiB free at /data/suiup/binaries, installs need about 1.00 GiB"
+        );
+        assert!(disk_space_status(path, None).is_ok());
+    }
+
+    #[test]
+    fn test_find_conflicting_binaries() {
+        let temp_dir = TempDir::new().unwrap();
+        let early = temp_dir.path().join("early");
+        let default_bin = temp_dir.path().join("default");
+        let late = temp_dir.path().join("late");
+        for dir in [&early, &default_bin, &late] {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        let make_exe = |path: PathBuf| {
+            fs::write(&path, b"").unwrap();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            }
+        };
+        make_exe(default_bin.join(exe_name("sui")));
+        make_exe(default_bin.join(exe_name("mvr")));
+        make_exe(early.join(exe_name("sui")));
+        // not managed by suiup in the default bin dir, so it does not shadow anything
+        make_exe(early.join(exe_name("walrus")));
+        // after the default bin dir, so it is shadowed instead
+        make_exe(late.join(exe_name("mvr")));
+
+        let paths = vec![early.clone(), default_bin.clone(), late];
+        let conflicts = find_conflicting_binaries(&paths, &default_bin);
+        assert_eq!(
+            conflicts,
+            vec![("sui".to_string(), early.join(exe_name("sui")))]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verify_binary_version() {
+        let sh = |script: &str| {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(script);
+            cmd
+        };
+        let timeout = Duration::from_secs(5);
+
+        assert_eq!(
+            verify_binary_version(sh("echo sui 1.40.1-abc"), "v1.40.1", timeout),
+            Ok("sui 1.40.1-abc".to_string())
+        );
+        assert_eq!(
+            verify_binary_version(sh("echo sui 1.41.0-abc"), "nightly", timeout),
+            Ok("sui 1.41.0-abc".to_string())
+        );
+        assert!(
+            verify_binary_version(sh("echo sui 1.39.3"), "v1.40.1", timeout)
+                .unwrap_err()
+                .starts_with("WARN: reports 'sui 1.39.3'")
+        );
+        assert!(
+            verify_binary_version(sh("echo 1.40.1; exit 3"), "v1.40.1", timeout)
+                .unwrap_err()
+                .starts_with("ERROR: exited with")
+        );
+        assert_eq!(
+            verify_binary_version(sh("true"), "v1.40.1", timeout),
+            Err("ERROR: did not report a version".to_string())
+        );
+        assert!(
+            verify_binary_version(sh("sleep 5"), "v1.40.1", Duration::from_millis(100))
+                .unwrap_err()
+                .contains("did not exit within")
+        );
+        assert!(
+            verify_binary_version(Command::new("/nonexistent/sui"), "v1.40.1", timeout)
+                .unwrap_err()
+                .contains("could not be run")
+        );
+    }
+
     #[test]
     fn test_check_suiup_data_dir_exists() {
         let temp_dir = TempDir::new().unwrap();
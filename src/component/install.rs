@@ -1,15 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::Path;
+
 use anyhow::{anyhow, Result};
-use std::fs::create_dir_all;
 
 use crate::commands::BinaryName;
-use crate::handlers::install::{install_from_nightly, install_from_release, install_standalone};
+use crate::handlers::disk::warn_if_low_disk_space;
+use crate::handlers::download::{parse_target, Checksum};
+use crate::handlers::install::{
+    ensure_writable_dir, install_from_nightly, install_from_release, install_standalone,
+    validate_also_binaries, validate_binary_alias,
+};
 use crate::paths::{binaries_dir, get_default_bin_dir};
 use crate::types::{Repo, Version};
 
 /// Install a component with the given parameters
+#[allow(clippy::too_many_arguments)]
 pub async fn install_component(
     name: BinaryName,
     network: String,
@@ -18,29 +25,73 @@ pub async fn install_component(
     debug: bool,
     yes: bool,
     github_token: Option<String>,
+    alias: Option<String>,
+    checksum: Option<String>,
+    also: Vec<String>,
+    target: Option<String>,
+    no_default: bool,
+    version_file: Option<&Path>,
 ) -> Result<()> {
-    // Ensure installation directories exist
-    let default_bin_dir = get_default_bin_dir();
-    create_dir_all(&default_bin_dir)?;
+    let checksum = checksum.map(|c| c.parse::<Checksum>()).transpose()?;
+    let checksum = checksum.as_ref();
+    if let Some(alias) = &alias {
+        validate_binary_alias(alias)?;
+    }
+    let alias = alias.as_deref();
+
+    // Ensure installation directories exist and are writable before any network work
+    ensure_writable_dir(&get_default_bin_dir()).map_err(|e| {
+        anyhow!("{e}. Fix its permissions or set SUIUP_DEFAULT_BIN_DIR to a writable directory")
+    })?;
 
     let installed_bins_dir = binaries_dir();
-    create_dir_all(&installed_bins_dir)?;
+    ensure_writable_dir(&installed_bins_dir).map_err(|e| {
+        anyhow!("{e}. Fix its permissions or use --data-dir to pick a writable directory")
+    })?;
+    warn_if_low_disk_space();
 
     if name != BinaryName::Sui && debug && nightly.is_none() {
         return Err(anyhow!("Debug flag is only available for the `sui` binary"));
     }
 
+    validate_also_binaries(&name.to_string(), &also)?;
+    if !also.is_empty() && (nightly.is_some() || name == BinaryName::Mvr) {
+        return Err(anyhow!(
+            "--also is only available when installing from a release archive"
+        ));
+    }
+
+    let target = target.map(|t| parse_target(&t)).transpose()?;
+    let target = target.as_deref();
+    if target.is_some() {
+        if nightly.is_some() || name == BinaryName::Mvr {
+            return Err(anyhow!(
+                "--target is only available when installing from a release archive"
+            ));
+        }
+        if alias.is_some() {
+            return Err(anyhow!(
+                "A binary downloaded with --target cannot be installed under a custom name"
+            ));
+        }
+    }
+
+    if nightly.is_some() && checksum.is_some() {
+        return Err(anyhow!(
+            "A checksum can only be verified for release downloads, not nightly builds"
+        ));
+    }
+
     if nightly.is_some() && version.is_some() {
         return Err(anyhow!(
             "Cannot install from nightly and a release at the same time. Remove the version or the nightly flag"
         ));
     }
 
-    match (&name, &nightly) {
+    let outcomes = match (&name, &nightly) {
         (BinaryName::Walrus, nightly) => {
-            create_dir_all(installed_bins_dir.join(network.clone()))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                vec![install_from_nightly(&name, branch, debug, yes, alias, no_default).await?]
             } else {
                 install_from_release(
                     name.to_string().as_str(),
@@ -50,14 +101,18 @@ pub async fn install_component(
                     yes,
                     Repo::Walrus,
                     github_token,
+                    alias,
+                    checksum,
+                    &also,
+                    target,
+                    no_default,
                 )
-                .await?;
+                .await?
             }
         }
         (BinaryName::WalrusSites, nightly) => {
-            create_dir_all(installed_bins_dir.join("mainnet"))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                vec![install_from_nightly(&name, branch, debug, yes, alias, no_default).await?]
             } else {
                 install_from_release(
                     name.to_string().as_str(),
@@ -67,30 +122,41 @@ pub async fn install_component(
                     yes,
                     Repo::WalrusSites,
                     github_token,
+                    alias,
+                    checksum,
+                    &also,
+                    target,
+                    no_default,
                 )
-                .await?;
+                .await?
             }
         }
         (BinaryName::Mvr, nightly) => {
-            create_dir_all(installed_bins_dir.join("standalone"))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                vec![install_from_nightly(&name, branch, debug, yes, alias, no_default).await?]
             } else {
-                install_standalone(
-                    version,
-                    match name {
-                        BinaryName::Mvr => Repo::Mvr,
-                        _ => {
-                            return Err(anyhow!("Invalid binary name for standalone installation"))
-                        }
-                    },
-                    yes,
-                )
-                .await?;
+                vec![
+                    install_standalone(
+                        version,
+                        match name {
+                            BinaryName::Mvr => Repo::Mvr,
+                            _ => {
+                                return Err(anyhow!(
+                                    "Invalid binary name for standalone installation"
+                                ))
+                            }
+                        },
+                        yes,
+                        alias,
+                        checksum,
+                        no_default,
+                    )
+                    .await?,
+                ]
             }
         }
         (_, Some(branch)) => {
-            install_from_nightly(&name, branch, debug, yes).await?;
+            vec![install_from_nightly(&name, branch, debug, yes, alias, no_default).await?]
         }
         _ => {
             install_from_release(
@@ -101,10 +167,22 @@ pub async fn install_component(
                 yes,
                 Repo::Sui,
                 github_token,
+                alias,
+                checksum,
+                &also,
+                target,
+                no_default,
             )
-            .await?;
+            .await?
         }
-    }
+    };
 
+    for outcome in &outcomes {
+        print!("{}", outcome.summary());
+    }
+    if let (Some(path), Some(outcome)) = (version_file, outcomes.first()) {
+        std::fs::write(path, format!("{}\n", outcome.resolved_version()))
+            .map_err(|e| anyhow!("Cannot write the version to {}: {e}", path.display()))?;
+    }
     Ok(())
 }
@@ -1,23 +1,59 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::commands::TABLE_FORMAT;
-use anyhow::Result;
+use crate::commands::{BinaryName, TABLE_FORMAT};
+use crate::handlers::pager::page_output;
+use anyhow::{anyhow, Result};
 use comfy_table::*;
+use serde::Serialize;
+
+/// An installable binary as printed by `suiup list --json`
+#[derive(Serialize)]
+struct ComponentInfo {
+    name: String,
+    repo: String,
+    /// Networks releases are published for; empty for standalone binaries
+    networks: Vec<&'static str>,
+    /// Whether the binary is versioned on its own rather than per network (e.g. mvr)
+    standalone: bool,
+}
 
 /// List all available components
-pub async fn list_components() -> Result<()> {
+pub async fn list_components(json: bool, no_pager: bool) -> Result<()> {
     let components = crate::handlers::available_components();
+    if json {
+        let components = components
+            .iter()
+            .map(|component| component_info(component))
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&components)?);
+        return Ok(());
+    }
+
+    let rows = components
+        .iter()
+        .map(|component| {
+            let binary: BinaryName = component.parse().map_err(|e: String| anyhow!(e))?;
+            Ok(vec![Cell::new(component), Cell::new(binary.release_type())])
+        })
+        .collect::<Result<Vec<Vec<Cell>>>>()?;
     let mut table = Table::new();
     table
         .load_preset(TABLE_FORMAT)
-        .set_header(vec![Cell::new("Available Binaries to Install")])
-        .add_rows(
-            components
-                .iter()
-                .map(|component| vec![Cell::new(component)])
-                .collect::<Vec<Vec<Cell>>>(),
-        );
-    println!("{table}");
-    Ok(())
+        .set_header(vec![
+            Cell::new("Available Binaries to Install"),
+            Cell::new("Type"),
+        ])
+        .add_rows(rows);
+    page_output(&format!("{table}\n"), no_pager)
+}
+
+fn component_info(component: &str) -> Result<ComponentInfo> {
+    let binary: BinaryName = component.parse().map_err(|e: String| anyhow!(e))?;
+    Ok(ComponentInfo {
+        name: binary.to_string(),
+        repo: binary.repo_url().to_string(),
+        networks: binary.repo().release_networks().to_vec(),
+        standalone: binary.is_standalone(),
+    })
 }
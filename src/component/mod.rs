@@ -6,11 +6,14 @@ mod install;
 mod list;
 mod remove;
 
-use anyhow::Result;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
 
 use crate::commands::{
     parse_component_with_version, BinaryName, CommandMetadata, ComponentCommands,
 };
+use crate::types::Config;
 
 /// ComponentManager handles all component-related operations
 pub struct ComponentManager {
@@ -26,37 +29,90 @@ impl ComponentManager {
     /// Handle component commands
     pub async fn handle_command(&self, cmd: ComponentCommands) -> Result<()> {
         match cmd {
-            ComponentCommands::Doctor => self.run_doctor_checks().await,
-            ComponentCommands::List => self.list_components().await,
+            ComponentCommands::Doctor {
+                fix,
+                check_disk,
+                check_token,
+            } => self.run_doctor_checks(fix, check_disk, check_token).await,
+            ComponentCommands::List { json, no_pager } => {
+                self.list_components(json, no_pager).await
+            }
             ComponentCommands::Add {
                 component,
                 nightly,
                 debug,
                 yes,
+                binary_name,
+                checksum,
+                also,
+                target,
+                no_default,
+                version_file,
             } => {
-                let command_metadata = parse_component_with_version(&component)?;
-                self.install_component(command_metadata, nightly, debug, yes)
-                    .await
+                let command_metadata = parse_component_with_version(&component, &Config::load()?)?;
+                self.install_component(
+                    command_metadata,
+                    nightly,
+                    debug,
+                    yes,
+                    binary_name,
+                    checksum,
+                    also,
+                    target,
+                    no_default,
+                    version_file,
+                )
+                .await
+            }
+            ComponentCommands::Remove { binaries, all } => {
+                self.remove_components(binaries, all).await
             }
-            ComponentCommands::Remove { binary } => self.remove_component(binary).await,
-            ComponentCommands::Cleanup { all, days, dry_run } => {
-                self.handle_cleanup(all, days, dry_run).await
+            ComponentCommands::Cleanup {
+                all,
+                days,
+                keep,
+                caches,
+                network,
+                component,
+                binaries,
+                dry_run,
+                summary,
+            } => {
+                let network = network.filter(|_| binaries);
+                let component = component.filter(|_| binaries);
+                if days.is_some() && network.is_some() && component.is_none() {
+                    bail!(
+                        "--days cannot be used with --network alone, which removes every binary of the network; add --component to only remove old binaries"
+                    );
+                }
+                let days = days.unwrap_or(30);
+                self.handle_cleanup(
+                    all, days, keep, caches, network, component, dry_run, summary,
+                )
+                .await
             }
         }
     }
 
     /// List all available components
-    async fn list_components(&self) -> Result<()> {
-        list::list_components().await
+    async fn list_components(&self, json: bool, no_pager: bool) -> Result<()> {
+        list::list_components(json, no_pager).await
     }
 
     /// Install a component
+    #[allow(clippy::too_many_arguments)]
     async fn install_component(
         &self,
         command_metadata: CommandMetadata,
         nightly: Option<String>,
         debug: bool,
         yes: bool,
+        alias: Option<String>,
+        checksum: Option<String>,
+        also: Vec<String>,
+        target: Option<String>,
+        no_default: bool,
+        version_file: Option<PathBuf>,
     ) -> Result<()> {
         let CommandMetadata {
             name,
@@ -71,22 +127,54 @@ impl ComponentManager {
             debug,
             yes,
             self.github_token.clone(),
+            alias,
+            checksum,
+            also,
+            target,
+            no_default,
+            version_file.as_deref(),
         )
         .await
     }
 
-    /// Remove a component
-    async fn remove_component(&self, binary: BinaryName) -> Result<()> {
-        remove::remove_component(binary).await
+    /// Remove one or more components, or all of them
+    async fn remove_components(&self, binaries: Vec<BinaryName>, all: bool) -> Result<()> {
+        remove::remove_components(binaries, all).await
     }
 
     /// Run diagnostic checks on the environment
-    pub async fn run_doctor_checks(&self) -> Result<()> {
-        doctor::run_doctor_checks().await
+    pub async fn run_doctor_checks(
+        &self,
+        fix: bool,
+        check_disk: bool,
+        check_token: bool,
+    ) -> Result<()> {
+        doctor::run_doctor_checks(fix, check_disk, check_token, self.github_token.as_deref()).await
     }
 
     /// Handle cleanup operations
-    async fn handle_cleanup(&self, all: bool, days: u32, dry_run: bool) -> Result<()> {
-        crate::handlers::cleanup::handle_cleanup(all, days, dry_run).await
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_cleanup(
+        &self,
+        all: bool,
+        days: u32,
+        keep: Option<usize>,
+        caches: bool,
+        network: Option<String>,
+        component: Option<BinaryName>,
+        dry_run: bool,
+        summary: bool,
+    ) -> Result<()> {
+        crate::handlers::cleanup::handle_cleanup(
+            all,
+            days,
+            keep,
+            caches,
+            network.as_deref(),
+            component.as_ref().map(BinaryName::to_str),
+            dry_run,
+            summary,
+        )
+        .await
     }
 }
@@ -10,9 +10,43 @@ use anyhow::{anyhow, Result};
 use tracing::debug;
 
 use crate::commands::BinaryName;
+use crate::handlers::{
+    active::sync_active_dir, available_components, batch::BatchResult, release_binary_path,
+};
 use crate::paths::{default_file_path, get_default_bin_dir};
 use crate::types::InstalledBinaries;
 
+/// Removes each of the given components, continuing with the next one when one of them fails.
+/// With `all`, every available component is removed and the default versions are cleared.
+pub async fn remove_components(binaries: Vec<BinaryName>, all: bool) -> Result<()> {
+    let binaries = if all {
+        available_components()
+            .iter()
+            .map(|c| c.parse().map_err(|e: String| anyhow!(e)))
+            .collect::<Result<Vec<BinaryName>>>()?
+    } else {
+        binaries
+    };
+
+    let mut batch = BatchResult::new("remove", true);
+    for binary in &binaries {
+        println!("Removing {binary}...");
+        batch.record(binary.to_str(), remove_component(binary.clone()).await);
+    }
+
+    if all {
+        // also drops defaults left behind by binaries removed by hand
+        let default_file = default_file_path()?;
+        File::create(&default_file)
+            .map_err(|_| anyhow!("Cannot create file: {}", default_file.display()))?
+            .write_all(b"{}")?;
+        sync_active_dir();
+        println!("Cleared the default binaries");
+    }
+
+    batch.finish()
+}
+
 /// Remove a component and its associated files
 pub async fn remove_component(binary: BinaryName) -> Result<()> {
     let mut installed_binaries = InstalledBinaries::new()?;
@@ -24,7 +58,7 @@ pub async fn remove_component(binary: BinaryName) -> Result<()> {
         .collect::<Vec<_>>();
 
     if binaries_to_remove.is_empty() {
-        println!("No binaries found to remove");
+        println!("No {binary} binaries found to remove");
         return Ok(());
     }
 
@@ -58,6 +92,32 @@ pub async fn remove_component(binary: BinaryName) -> Result<()> {
             debug!("File removed: {p}");
             println!("Removed binary: {} from {p}", binary.binary_name);
         }
+
+        // Remove the companions extracted with it by `install --also`
+        for companion in &binary.companions {
+            let path = release_binary_path(
+                companion,
+                &binary.network_release,
+                &binary.version,
+                binary.target.as_deref(),
+            );
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| anyhow!("Cannot remove file: {e}"))?;
+                println!("Removed binary: {companion} from {}", path.display());
+            }
+        }
+
+        // Remove the copy installed under a custom name, if any
+        if let Some(alias) = binary.alias.as_ref() {
+            let alias_path = get_default_bin_dir().join(alias);
+            #[cfg(target_os = "windows")]
+            let alias_path = alias_path.with_extension("exe");
+            if alias_path.exists() {
+                std::fs::remove_file(&alias_path)
+                    .map_err(|e| anyhow!("Cannot remove file: {e}"))?;
+                println!("Removed {alias} from default binaries folder");
+            }
+        }
     }
 
     // Remove the binaries from the default-bin folder
@@ -65,6 +125,24 @@ pub async fn remove_component(binary: BinaryName) -> Result<()> {
         .iter()
         .map(|x| &x.binary_name)
         .collect::<HashSet<_>>();
+    let companions = binaries_to_remove
+        .iter()
+        .flat_map(|x| &x.companions)
+        .collect::<HashSet<_>>();
+
+    for companion in companions {
+        let default_bin_path = get_default_bin_dir().join(companion);
+        #[cfg(target_os = "windows")]
+        let default_bin_path = default_bin_path.with_extension("exe");
+        if default_bin_path.exists() {
+            std::fs::remove_file(&default_bin_path)
+                .map_err(|e| anyhow!("Cannot remove file: {e}"))?;
+            debug!(
+                "Removed {} from default binaries folder",
+                default_bin_path.display()
+            );
+        }
+    }
 
     for binary in default_binaries_to_remove {
         let default_bin_path = get_default_bin_dir().join(binary);
@@ -85,11 +163,13 @@ pub async fn remove_component(binary: BinaryName) -> Result<()> {
     File::create(&default_file)
         .map_err(|_| anyhow!("Cannot create file: {}", default_file.display()))?
         .write_all(serde_json::to_string_pretty(&default_binaries)?.as_bytes())?;
+    sync_active_dir();
 
     // Update installed binaries metadata
     installed_binaries.remove_binary(&binary.to_string());
     debug!("Removed {binary} from installed_binaries JSON file. Saving updated data");
     installed_binaries.save_to_file()?;
+    println!("Removed {binary}");
 
     Ok(())
 }
@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use tracing::debug;
+
+use crate::handlers::default_binary_source;
+use crate::paths::{binaries_dir, default_file_path, get_default_bin_dir};
+use crate::symbols::symbols;
+use crate::types::{Binaries, Config, Version};
+
+/// Regenerates the `active_dir` from the config file, when one is set: the symlinks in it that
+/// point into the binaries folder are removed, then one is created per default binary, pointing
+/// at its versioned file. Other files and links are left alone.
+///
+/// Unlike the default bin folder, which holds copies and is often shared with other tools (e.g.
+/// `~/.local/bin`), the active folder only holds these links, so putting it first in `PATH`
+/// selects exactly the default versions.
+///
+/// The default version file is already written when this runs, so a failure (e.g. no symlink
+/// privilege on Windows) is only reported as a warning.
+pub fn sync_active_dir() {
+    if let Err(e) = sync() {
+        eprintln!("[{}] Cannot update the active folder: {e}", symbols().warn);
+    }
+}
+
+fn sync() -> Result<()> {
+    let Some(dir) = Config::load()?.active_dir else {
+        return Ok(());
+    };
+    if !dir.is_absolute() {
+        bail!(
+            "active_dir in the config file must be an absolute path, not {}",
+            dir.display()
+        );
+    }
+    if same_dir(&dir, &get_default_bin_dir()) {
+        bail!(
+            "active_dir in the config file cannot be the default bin folder {}",
+            dir.display()
+        );
+    }
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| anyhow!("Cannot create the active folder {}: {e}", dir.display()))?;
+
+    let binaries_dir = binaries_dir();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if !path.symlink_metadata()?.file_type().is_symlink() {
+            continue;
+        }
+        // links of other tools are kept
+        if std::fs::read_link(&path)?.starts_with(&binaries_dir) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+
+    let defaults: BTreeMap<String, (String, Version, bool)> =
+        serde_json::from_str(&std::fs::read_to_string(default_file_path()?)?)?;
+    for binary in Binaries::from(defaults).binaries {
+        let src = default_binary_source(&binary);
+        if !src.is_file() {
+            debug!(
+                "Not linking {}: {} is missing",
+                binary.binary_name,
+                src.display()
+            );
+            continue;
+        }
+        let link = dir.join(&binary.binary_name);
+        #[cfg(windows)]
+        let link = link.with_extension("exe");
+        symlink(&src, &link)
+            .map_err(|e| anyhow!("Cannot link {} to {}: {e}", link.display(), src.display()))?;
+    }
+    Ok(())
+}
+
+/// Whether `a` and `b` are the same folder, comparing the resolved paths when both exist
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(unix)]
+fn symlink(src: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(src, link)
+}
+
+/// Needs Developer Mode or administrator rights
+#[cfg(windows)]
+fn symlink(src: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(src, link)
+}
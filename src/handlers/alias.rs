@@ -0,0 +1,87 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::commands::parse_component_with_version;
+use crate::handlers::available_components;
+use crate::types::Config;
+
+/// Handles `alias set`: checks the name and the spec, then stores the alias in the config file
+pub fn handle_alias_set(name: &str, spec: &str) -> Result<()> {
+    validate_alias_name(name)?;
+    let spec = spec.trim();
+    let mut config = Config::load()?;
+    if config.aliases.contains_key(spec) {
+        bail!("'{spec}' is an alias itself, use the spec it stands for instead");
+    }
+    parse_component_with_version(spec, &config)
+        .map_err(|e| anyhow!("Invalid spec '{spec}': {e}"))?;
+
+    match config.aliases.insert(name.to_string(), spec.to_string()) {
+        Some(previous) if previous != spec => {
+            println!("{name} now stands for {spec} (was {previous})")
+        }
+        _ => println!("{name} stands for {spec}"),
+    }
+    config.save()
+}
+
+/// Handles `alias remove`
+pub fn handle_alias_remove(name: &str) -> Result<()> {
+    let mut config = Config::load()?;
+    let spec = config
+        .aliases
+        .remove(name)
+        .ok_or_else(|| anyhow!("No alias named '{name}'. Use `suiup alias list` to see them"))?;
+    config.save()?;
+    println!("Removed {name} ({spec})");
+    Ok(())
+}
+
+/// Handles `alias list`
+pub fn handle_alias_list() -> Result<()> {
+    let config = Config::load()?;
+    if config.aliases.is_empty() {
+        println!("No aliases. Add one with `suiup alias set <name> <spec>`");
+    }
+    for (name, spec) in &config.aliases {
+        println!("{name} -> {spec}");
+    }
+    Ok(())
+}
+
+/// Checks that `name` can be told apart from a spec: it is not a binary name and has no spec
+/// separators in it
+fn validate_alias_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.starts_with('-')
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        bail!("Invalid alias '{name}': use letters, digits, '.', '_' and '-'");
+    }
+    if available_components().contains(&name.to_lowercase().as_str()) {
+        bail!("Invalid alias '{name}': it is the name of a binary");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_alias_name() {
+        assert!(validate_alias_name("my-stable").is_ok());
+        assert!(validate_alias_name("team_v1.39").is_ok());
+
+        assert!(validate_alias_name("").is_err());
+        assert!(validate_alias_name("-x").is_err());
+        assert!(validate_alias_name("my@stable").is_err());
+        assert!(validate_alias_name("my stable").is_err());
+        assert!(validate_alias_name("sui").is_err());
+        assert!(validate_alias_name("Site-Builder").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Error, Result};
+use comfy_table::Table;
+use std::fmt;
+
+use crate::commands::TABLE_FORMAT;
+use crate::symbols::symbols;
+
+/// Collects the result of each item of a command run over several binaries (`switch a b`,
+/// `remove a b`, `install --manifest`), so one failure does not hide the others.
+pub struct BatchResult {
+    verb: &'static str,
+    keep_going: bool,
+    results: Vec<(String, Option<String>)>,
+}
+
+impl BatchResult {
+    /// A batch for `verb` (e.g. `switch`), which stops at the first failure unless `keep_going`
+    pub fn new(verb: &'static str, keep_going: bool) -> Self {
+        Self {
+            verb,
+            keep_going,
+            results: vec![],
+        }
+    }
+
+    /// Records the result for `item`, printing the error if it failed. Returns whether the batch
+    /// should go on with the next item.
+    pub fn record(&mut self, item: &str, result: Result<()>) -> bool {
+        let error = result.err().map(|e| {
+            eprintln!("Failed to {} {item}: {e}", self.verb);
+            e.to_string()
+        });
+        let go_on = error.is_none() || self.keep_going;
+        self.results.push((item.to_string(), error));
+        go_on
+    }
+
+    /// Number of items recorded
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Number of items that succeeded
+    pub fn succeeded(&self) -> usize {
+        self.results.iter().filter(|(_, e)| e.is_none()).count()
+    }
+
+    /// Table with the outcome of every item
+    pub fn summary_table(&self) -> String {
+        let symbols = symbols();
+        let mut table = Table::new();
+        table
+            .load_preset(TABLE_FORMAT)
+            .set_header(vec!["Binary", "Result"])
+            .add_rows(self.results.iter().map(|(item, error)| {
+                let result = match error {
+                    None => symbols.ok.to_string(),
+                    Some(e) => format!("{} {e}", symbols.fail),
+                };
+                vec![item.clone(), result]
+            }));
+        table.to_string()
+    }
+
+    /// Prints the summary table when there was more than one item, and fails with a
+    /// [`BatchError`] if any item failed
+    pub fn finish(self) -> Result<()> {
+        if self.results.len() > 1 {
+            println!("{}", self.summary_table());
+        }
+        let failed: Vec<String> = self
+            .results
+            .into_iter()
+            .filter_map(|(item, error)| error.map(|_| item))
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(BatchError {
+                verb: self.verb,
+                failed,
+            }))
+        }
+    }
+}
+
+/// The items of a batch that failed. suiup exits with their count, capped at 255.
+#[derive(Debug)]
+pub struct BatchError {
+    verb: &'static str,
+    pub failed: Vec<String>,
+}
+
+impl BatchError {
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        self.failed.len().min(255) as i32
+    }
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Could not {}: {}", self.verb, self.failed.join(", "))
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_batch_result_collects_failures() {
+        let mut batch = BatchResult::new("remove", true);
+        assert!(batch.record("sui", Ok(())));
+        assert!(batch.record("walrus", Err(anyhow!("file is busy"))));
+        assert!(batch.record("mvr", Err(anyhow!("not installed"))));
+        assert_eq!((batch.len(), batch.succeeded()), (3, 1));
+        assert!(batch.summary_table().contains("file is busy"));
+
+        let err = batch.finish().unwrap_err();
+        assert_eq!(err.to_string(), "Could not remove: walrus, mvr");
+        assert_eq!(err.downcast_ref::<BatchError>().unwrap().exit_code(), 2);
+
+        let mut batch = BatchResult::new("install", false);
+        assert!(!batch.record("sui", Err(anyhow!("offline"))));
+        assert!(BatchResult::new("install", false).finish().is_ok());
+    }
+
+    #[test]
+    fn test_batch_exit_code_is_capped() {
+        let error = BatchError {
+            verb: "install",
+            failed: vec!["sui".to_string(); 300],
+        };
+        assert_eq!(error.exit_code(), 255);
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    commands::{parse_component_with_version, resolve_pinned_spec, ComponentCommands},
+    handle_commands::handle_cmd,
+    handlers::batch::BatchResult,
+    paths::release_archive_dir,
+    types::Config,
+};
+
+/// Downloads and extracts every spec in turn, like `suiup install --no-default -y`, and reports
+/// the release archives that were fetched. Specs are validated before anything is downloaded.
+/// Installs run one after the other, as specs resolving to the same archive or binary would
+/// otherwise write the same files and the installed binaries list at once.
+pub async fn handle_cache_warm(specs: &[String], github_token: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    let specs = specs
+        .iter()
+        .map(|spec| {
+            let spec = resolve_pinned_spec(spec, &config)?;
+            parse_component_with_version(&spec, &config)
+                .map_err(|e| anyhow!("Invalid spec '{spec}': {e}"))?;
+            Ok(spec)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let before = archive_names();
+    let mut batch = BatchResult::new("cache", true);
+    for spec in &specs {
+        let result = handle_cmd(
+            ComponentCommands::Add {
+                component: spec.clone(),
+                nightly: None,
+                debug: false,
+                yes: true,
+                binary_name: None,
+                checksum: None,
+                also: vec![],
+                target: None,
+                no_default: true,
+                version_file: None,
+            },
+            github_token.clone(),
+        )
+        .await;
+        batch.record(spec, result);
+    }
+
+    let fetched: Vec<String> = archive_names().difference(&before).cloned().collect();
+    if fetched.is_empty() {
+        println!("No release archive was fetched, they were all cached already");
+    } else {
+        println!("Fetched {} release archives:", fetched.len());
+        for name in fetched {
+            println!("  {name}");
+        }
+    }
+    batch.finish()
+}
+
+/// Names of the release archives currently in the cache
+fn archive_names() -> BTreeSet<String> {
+    std::fs::read_dir(release_archive_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
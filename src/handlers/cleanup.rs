@@ -1,17 +1,63 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 
-use crate::paths::release_archive_dir;
+use crate::commands::parse_component_with_version;
+use crate::handlers::download::sidecar_path;
+use crate::handlers::version::{extract_version_from_release, find_version_file, SemVer};
+use crate::handlers::{binary_source_path, exe_suffix};
+use crate::paths::{
+    binaries_dir, default_file_path, get_default_bin_dir, get_suiup_cache_dir, release_archive_dir,
+};
+use crate::types::{BinaryVersion, Config, InstalledBinaries, Version, NETWORKS};
+
+/// Default versions as stored in `default_version.json`: binary -> (network, version, debug)
+pub type Defaults = BTreeMap<String, (String, Version, bool)>;
+
+/// Versions pinned by `.<binary>-version` files: binary -> (network, version). A pin without a
+/// version (e.g. `testnet`) keeps every version of that network.
+pub type Pins = BTreeMap<String, (String, Option<Version>)>;
+
+/// Prints a line about a single file or step, which `--summary` leaves out
+fn report(summary: bool, line: String) {
+    if !summary {
+        println!("{line}");
+    }
+}
+
+/// Handles the `cleanup` command. With `summary`, only the cache sizes and the totals are printed.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_cleanup(
+    all: bool,
+    days: u32,
+    keep: Option<usize>,
+    caches: bool,
+    network: Option<&str>,
+    component: Option<&str>,
+    dry_run: bool,
+    summary: bool,
+) -> Result<()> {
+    if caches || all {
+        clean_release_list_caches(dry_run, summary)?;
+    }
+
+    if let Some(component) = component {
+        return prune_binaries(component, network, days, dry_run, summary);
+    }
+    if let Some(network) = network {
+        return clean_network(network, dry_run, summary);
+    }
 
-/// Handles the `cleanup` command
-pub async fn handle_cleanup(all: bool, days: u32, dry_run: bool) -> Result<()> {
     let release_archive_dir = release_archive_dir();
-    println!(
-        "Release archives directory: {}",
-        release_archive_dir.display()
+    report(
+        summary,
+        format!(
+            "Release archives directory: {}",
+            release_archive_dir.display()
+        ),
     );
 
     if !release_archive_dir.exists() {
@@ -27,25 +73,63 @@ pub async fn handle_cleanup(all: bool, days: u32, dry_run: bool) -> Result<()> {
     );
 
     if all {
+        let mut files = vec![];
+        collect_files(&release_archive_dir, &mut files)?;
         if dry_run {
-            println!("Would remove all release archives in cache directory (dry run)");
+            report(
+                summary,
+                "Would remove all release archives in cache directory (dry run)".to_string(),
+            );
+            if summary {
+                println!(
+                    "Would remove {} files totaling {} (dry run)",
+                    files.len(),
+                    format_file_size(total_size_before)
+                );
+                println!("Cache size after cleanup would be: {}", format_file_size(0));
+            }
         } else {
-            println!("Removing all release archives in cache directory...");
+            report(
+                summary,
+                "Removing all release archives in cache directory...".to_string(),
+            );
             if release_archive_dir.exists() {
                 fs::remove_dir_all(&release_archive_dir)?;
                 fs::create_dir_all(&release_archive_dir)?;
             }
-            println!("{}", "Cache cleared successfully.");
+            report(summary, "Cache cleared successfully.".to_string());
+            if summary {
+                println!(
+                    "Cleanup complete. {} files removed, {} freed",
+                    files.len(),
+                    format_file_size(total_size_before)
+                );
+                let total_size_after = calculate_dir_size(&release_archive_dir)?;
+                println!("New cache size: {}", format_file_size(total_size_after));
+            }
         }
         return Ok(());
     }
 
+    if let Some(keep) = keep {
+        return prune_archives(
+            &release_archive_dir,
+            keep,
+            total_size_before,
+            dry_run,
+            summary,
+        );
+    }
+
     // Calculate cutoff duration
     let cutoff_duration = Duration::from_secs(60 * 60 * 24 * days as u64); // days to seconds
     let mut cleaned_size = 0;
     let mut files_removed = 0;
 
-    println!("Removing release archives older than {} days...", days);
+    report(
+        summary,
+        format!("Removing release archives older than {days} days..."),
+    );
 
     // Process release_archive_dir
     if release_archive_dir.exists() {
@@ -72,18 +156,24 @@ pub async fn handle_cleanup(all: bool, days: u32, dry_run: bool) -> Result<()> {
                 files_removed += 1;
 
                 if dry_run {
-                    println!(
-                        "Would remove: {} ({} days old, {})",
-                        path.display(),
-                        days_old,
-                        format_file_size(file_size)
+                    report(
+                        summary,
+                        format!(
+                            "Would remove: {} ({} days old, {})",
+                            path.display(),
+                            days_old,
+                            format_file_size(file_size)
+                        ),
                     );
                 } else {
-                    println!(
-                        "Removing: {} ({} days old, {})",
-                        path.display(),
-                        days_old,
-                        format_file_size(file_size)
+                    report(
+                        summary,
+                        format!(
+                            "Removing: {} ({} days old, {})",
+                            path.display(),
+                            days_old,
+                            format_file_size(file_size)
+                        ),
                     );
                     fs::remove_file(path)?;
                 }
@@ -98,10 +188,15 @@ pub async fn handle_cleanup(all: bool, days: u32, dry_run: bool) -> Result<()> {
             files_removed,
             format_file_size(cleaned_size)
         );
+        if summary {
+            println!(
+                "Cache size after cleanup would be: {}",
+                format_file_size(total_size_before.saturating_sub(cleaned_size))
+            );
+        }
     } else {
         println!(
-            "{} {} files removed, {} freed",
-            "Cleanup complete.",
+            "Cleanup complete. {} files removed, {} freed",
             files_removed,
             format_file_size(cleaned_size)
         );
@@ -113,6 +208,540 @@ pub async fn handle_cleanup(all: bool, days: u32, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Removes the cached release lists (`releases_*.txt`) and their ETags (`etag_*.txt`), so the next
+/// install fetches fresh release metadata.
+fn clean_release_list_caches(dry_run: bool, summary: bool) -> Result<()> {
+    let cache_dir = get_suiup_cache_dir();
+    if !cache_dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(&cache_dir)? {
+        let path = entry?.path();
+        let is_release_cache = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+            (n.starts_with("releases_") || n.starts_with("etag_")) && n.ends_with(".txt")
+        });
+        if !path.is_file() || !is_release_cache {
+            continue;
+        }
+        if dry_run {
+            report(
+                summary,
+                format!("Would remove release list cache: {}", path.display()),
+            );
+        } else {
+            report(
+                summary,
+                format!("Removing release list cache: {}", path.display()),
+            );
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Keeps only the `keep` newest archives per binary and network, regardless of their age.
+/// `total_size_before` is the size of the archives folder before pruning.
+fn prune_archives(
+    release_archive_dir: &PathBuf,
+    keep: usize,
+    total_size_before: u64,
+    dry_run: bool,
+    summary: bool,
+) -> Result<()> {
+    report(
+        summary,
+        format!("Keeping the {keep} newest release archives per binary and network..."),
+    );
+
+    let mut names = vec![];
+    for entry in fs::read_dir(release_archive_dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            names.push(name.to_string());
+        }
+    }
+
+    let mut cleaned_size = 0;
+    let mut files_removed = 0;
+    for name in archives_to_prune(&names, keep) {
+        let path = release_archive_dir.join(name);
+        let file_size = fs::metadata(&path)?.len();
+        cleaned_size += file_size;
+        files_removed += 1;
+
+        if dry_run {
+            report(
+                summary,
+                format!(
+                    "Would remove: {} ({})",
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+        } else {
+            report(
+                summary,
+                format!(
+                    "Removing: {} ({})",
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+            for sidecar in [sidecar_path(&path, "md5"), sidecar_path(&path, "etag")] {
+                if sidecar.exists() {
+                    fs::remove_file(sidecar)?;
+                }
+            }
+            fs::remove_file(path)?;
+        }
+    }
+
+    if dry_run {
+        println!(
+            "Would remove {} files totaling {} (dry run)",
+            files_removed,
+            format_file_size(cleaned_size)
+        );
+        if summary {
+            println!(
+                "Cache size after cleanup would be: {}",
+                format_file_size(total_size_before.saturating_sub(cleaned_size))
+            );
+        }
+    } else {
+        println!(
+            "Cleanup complete. {} files removed, {} freed",
+            files_removed,
+            format_file_size(cleaned_size)
+        );
+
+        let total_size_after = calculate_dir_size(release_archive_dir)?;
+        println!("New cache size: {}", format_file_size(total_size_after));
+    }
+
+    Ok(())
+}
+
+/// Removes the extracted binaries and the release archives of a single network (or nightly
+/// branch), leaving the other networks untouched.
+fn clean_network(network: &str, dry_run: bool, summary: bool) -> Result<()> {
+    let mut installed_binaries = InstalledBinaries::new()?;
+    check_network_name(network, &installed_binaries)?;
+    let defaults: Defaults = serde_json::from_str(&fs::read_to_string(default_file_path()?)?)?;
+    let held: Vec<&str> = defaults
+        .iter()
+        .filter(|(_, (n, _, _))| n == network)
+        .map(|(binary, _)| binary.as_str())
+        .collect();
+    if !held.is_empty() {
+        bail!(
+            "{network} holds the default version of {}. Switch to another version with `suiup switch` first",
+            held.join(", ")
+        );
+    }
+
+    let network_dir = binaries_dir().join(network);
+    report(
+        summary,
+        format!(
+            "Removing binaries and release archives for {network} from {}",
+            network_dir.display()
+        ),
+    );
+
+    let mut files = vec![];
+    if network_dir.is_dir() {
+        collect_files(&network_dir, &mut files)?;
+    }
+    // with the flat layout, the binaries are not in a network folder
+    for binary in installed_binaries.binaries() {
+        if binary.network_release != network {
+            continue;
+        }
+        let path = binary
+            .path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| binary_source_path(binary));
+        if path.is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    let release_archive_dir = release_archive_dir();
+    if release_archive_dir.is_dir() {
+        for entry in fs::read_dir(&release_archive_dir)? {
+            let path = entry?.path();
+            let is_network_archive = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.contains(&format!("-{network}-v")));
+            if path.is_file() && is_network_archive {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut cleaned_size = 0;
+    for path in &files {
+        let file_size = fs::metadata(path)?.len();
+        cleaned_size += file_size;
+        if dry_run {
+            report(
+                summary,
+                format!(
+                    "Would remove: {} ({})",
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+        } else {
+            report(
+                summary,
+                format!(
+                    "Removing: {} ({})",
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+            fs::remove_file(path)?;
+        }
+    }
+
+    if dry_run {
+        println!(
+            "Would remove {} files totaling {} (dry run)",
+            files.len(),
+            format_file_size(cleaned_size)
+        );
+        return Ok(());
+    }
+
+    if network_dir.is_dir() {
+        fs::remove_dir_all(&network_dir)?;
+    }
+    installed_binaries.remove_network(network);
+    installed_binaries.save_to_file()?;
+
+    println!(
+        "Cleanup complete. {} files removed, {} freed",
+        files.len(),
+        format_file_size(cleaned_size)
+    );
+    Ok(())
+}
+
+/// Checks that `network` names a network or a branch suiup installed from, so it can be joined to
+/// the binaries folder: `..`, an empty name or an absolute path would point outside of it.
+fn check_network_name(network: &str, installed_binaries: &InstalledBinaries) -> Result<()> {
+    let is_plain = !network.is_empty()
+        && !network.contains('\\')
+        && Path::new(network)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    let is_known = NETWORKS.contains(&network)
+        || installed_binaries
+            .binaries()
+            .iter()
+            .any(|b| b.network_release == network)
+        || binaries_dir().join(network).is_dir();
+    if !is_plain || !is_known {
+        bail!("Unknown network or branch '{network}'");
+    }
+    Ok(())
+}
+
+/// Removes the extracted binaries of `component` (optionally of a single network) that were
+/// installed more than `days` ago. The default version of the component is always kept.
+fn prune_binaries(
+    component: &str,
+    network: Option<&str>,
+    days: u32,
+    dry_run: bool,
+    summary: bool,
+) -> Result<()> {
+    let cutoff_duration = Duration::from_secs(60 * 60 * 24 * days as u64);
+    report(
+        summary,
+        format!("Removing {component} binaries installed more than {days} days ago..."),
+    );
+
+    let default = fs::read_to_string(default_file_path()?)?;
+    let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&default)?;
+    let default = default.get(component);
+
+    let mut installed_binaries = InstalledBinaries::new()?;
+    let mut to_remove = vec![];
+    for binary in installed_binaries.binaries() {
+        if binary.binary_name != component || network.is_some_and(|n| n != binary.network_release) {
+            continue;
+        }
+        let is_default = default.is_some_and(|(network, version, debug)| {
+            *network == binary.network_release
+                && *version == binary.version
+                && *debug == binary.debug
+        });
+        if is_default {
+            report(
+                summary,
+                format!("Keeping default: {binary} ({})", binary.network_release),
+            );
+            continue;
+        }
+
+        let path = binary
+            .path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| binary_source_path(binary));
+        // fall back to the file's age for entries recorded before install times were tracked
+        let installed_at = binary
+            .installed_at
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+            .or_else(|| fs::metadata(&path).and_then(|m| m.modified()).ok());
+        let age = installed_at
+            .and_then(|t| SystemTime::now().duration_since(t).ok())
+            .unwrap_or(Duration::MAX);
+        if age > cutoff_duration {
+            to_remove.push((binary.clone(), path));
+        }
+    }
+
+    let mut cleaned_size = 0;
+    for (binary, path) in &to_remove {
+        let file_size = fs::metadata(path).map(|m| m.len()).unwrap_or_default();
+        cleaned_size += file_size;
+        if dry_run {
+            report(
+                summary,
+                format!(
+                    "Would remove: {binary} ({}) at {} ({})",
+                    binary.network_release,
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+        } else {
+            report(
+                summary,
+                format!(
+                    "Removing: {binary} ({}) at {} ({})",
+                    binary.network_release,
+                    path.display(),
+                    format_file_size(file_size)
+                ),
+            );
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            installed_binaries.remove_binary_version(binary);
+        }
+    }
+
+    if dry_run {
+        println!(
+            "Would remove {} binaries totaling {} (dry run)",
+            to_remove.len(),
+            format_file_size(cleaned_size)
+        );
+    } else {
+        installed_binaries.save_to_file()?;
+        println!(
+            "Cleanup complete. {} binaries removed, {} freed",
+            to_remove.len(),
+            format_file_size(cleaned_size)
+        );
+    }
+    Ok(())
+}
+
+/// Handles the `prune` command: removes every installed binary that is neither a default one nor
+/// pinned by a `.<binary>-version` file, then the release archives none of the kept binaries
+/// come from.
+pub fn handle_prune(dry_run: bool) -> Result<()> {
+    let defaults: Defaults = serde_json::from_str(&fs::read_to_string(default_file_path()?)?)?;
+    let mut installed_binaries = InstalledBinaries::new()?;
+    let config = Config::load()?;
+
+    let mut pins = Pins::new();
+    let cwd = std::env::current_dir()?;
+    for binary in installed_binaries.binaries() {
+        if pins.contains_key(&binary.binary_name) {
+            continue;
+        }
+        let Some((path, spec)) = find_version_file(&cwd, &binary.binary_name)? else {
+            continue;
+        };
+        let metadata =
+            parse_component_with_version(&format!("{}@{spec}", binary.binary_name), &config)
+                .map_err(|e| anyhow!("Invalid version in {}: {e}", path.display()))?;
+        println!(
+            "Keeping pinned: {}@{spec} ({})",
+            binary.binary_name,
+            path.display()
+        );
+        pins.insert(
+            binary.binary_name.clone(),
+            (metadata.network, metadata.version),
+        );
+    }
+
+    let (to_remove, kept): (Vec<BinaryVersion>, Vec<BinaryVersion>) = installed_binaries
+        .binaries()
+        .iter()
+        .cloned()
+        .partition(|binary| is_prunable(binary, &defaults, &pins));
+
+    let mut files = vec![];
+    for binary in &to_remove {
+        let path = binary
+            .path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| binary_source_path(binary));
+        files.push((binary.to_string(), path));
+
+        // the copy installed under a custom name, unless a kept version uses the same name
+        if let Some(alias) = binary.alias.as_ref() {
+            let alias_path = get_default_bin_dir().join(format!("{alias}{}", exe_suffix(None)));
+            if alias_path.is_file() && !kept.iter().any(|b| b.alias == binary.alias) {
+                files.push((alias.clone(), alias_path));
+            }
+        }
+    }
+    let mut archives = 0;
+    let release_archive_dir = release_archive_dir();
+    if release_archive_dir.is_dir() {
+        for entry in fs::read_dir(&release_archive_dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if path.is_file() && !kept.iter().any(|b| archive_of(name, b)) {
+                // checksums and ETags go with their archive, but are not counted as archives
+                if !name.ends_with(".md5") && !name.ends_with(".etag") {
+                    archives += 1;
+                }
+                files.push((name.to_string(), path));
+            }
+        }
+    }
+
+    let mut reclaimed = 0;
+    for (what, path) in &files {
+        let file_size = fs::metadata(path).map(|m| m.len()).unwrap_or_default();
+        reclaimed += file_size;
+        if dry_run {
+            println!(
+                "Would remove: {what} at {} ({})",
+                path.display(),
+                format_file_size(file_size)
+            );
+        } else {
+            println!(
+                "Removing: {what} at {} ({})",
+                path.display(),
+                format_file_size(file_size)
+            );
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+
+    if dry_run {
+        println!(
+            "Would remove {} binaries and {archives} archives, reclaiming {} (dry run)",
+            to_remove.len(),
+            format_file_size(reclaimed)
+        );
+    } else {
+        for binary in &to_remove {
+            installed_binaries.remove_binary_version(binary);
+        }
+        installed_binaries.save_to_file()?;
+        println!(
+            "Prune complete. {} binaries and {archives} archives removed, {} reclaimed",
+            to_remove.len(),
+            format_file_size(reclaimed)
+        );
+    }
+    Ok(())
+}
+
+/// Whether `binary` can be pruned: it is not the default version of its binary, nor pinned
+pub fn is_prunable(binary: &BinaryVersion, defaults: &Defaults, pins: &Pins) -> bool {
+    let is_default = defaults
+        .get(&binary.binary_name)
+        .is_some_and(|(network, version, debug)| {
+            *network == binary.network_release
+                && *version == binary.version
+                && *debug == binary.debug
+                && binary.target.is_none()
+        });
+    let is_pinned = pins
+        .get(&binary.binary_name)
+        .is_some_and(|(network, version)| {
+            *network == binary.network_release
+                && version.as_ref().is_none_or(|v| {
+                    v.trim_start_matches('v') == binary.version.trim_start_matches('v')
+                })
+        });
+    !is_default && !is_pinned
+}
+
+/// Whether the release archive `name` (or its checksum/ETag file) is the one `binary` was
+/// extracted from
+fn archive_of(name: &str, binary: &BinaryVersion) -> bool {
+    let version = binary.version.trim_start_matches('v');
+    name.starts_with(&format!("{}-", binary.binary_name))
+        && name.contains(&format!("-{}-v{version}-", binary.network_release))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the archive names that fall outside the `keep` newest versions of their binary and
+/// network, e.g. `sui-testnet` for `sui-testnet-v1.40.1-ubuntu-x86_64.tgz`. Versions are compared
+/// numerically, so `v1.10.0` is newer than `v1.9.0`. Files without a version are never pruned.
+pub fn archives_to_prune(names: &[String], keep: usize) -> Vec<&str> {
+    let mut groups: BTreeMap<&str, Vec<(SemVer, &str)>> = BTreeMap::new();
+    for name in names {
+        // checksums and ETags are removed together with their archive
+        if name.ends_with(".md5") || name.ends_with(".etag") {
+            continue;
+        }
+        let Ok(version) = extract_version_from_release(name) else {
+            continue;
+        };
+        let Some(idx) = name.find(&version) else {
+            continue;
+        };
+        groups
+            .entry(name[..idx].trim_end_matches('-'))
+            .or_default()
+            .push((SemVer::lenient(&version), name.as_str()));
+    }
+
+    let mut prune = vec![];
+    for archives in groups.values_mut() {
+        archives.sort_by(|a, b| b.cmp(a));
+        prune.extend(archives.iter().skip(keep).map(|(_, name)| *name));
+    }
+    prune
+}
+
 fn calculate_dir_size(dir: &PathBuf) -> Result<u64> {
     if !dir.exists() {
         return Ok(0);
@@ -0,0 +1,72 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::paths::{display_path, get_config_home, get_data_home, resolve_home};
+use anyhow::{anyhow, bail, Error};
+use clap_complete::{generate, Shell};
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::PathBuf;
+
+/// Returns the file the completions for `shell` are installed to, in the directory the shell
+/// loads completions from by convention
+pub fn completion_file(shell: Shell) -> Result<PathBuf, Error> {
+    match shell {
+        Shell::Bash => Ok(get_data_home()?
+            .join("bash-completion")
+            .join("completions")
+            .join("suiup")),
+        Shell::Fish => Ok(get_config_home()?
+            .join("fish")
+            .join("completions")
+            .join("suiup.fish")),
+        Shell::Zsh => Ok(zsh_completion_dir()?.join("_suiup")),
+        _ => bail!(
+            "Cannot determine the completion directory for {shell}; redirect the output of \
+             `suiup completion {shell}` to a file instead"
+        ),
+    }
+}
+
+/// The first `FPATH` entry under the home directory if zsh exported one, otherwise
+/// `~/.zsh/completions`
+fn zsh_completion_dir() -> Result<PathBuf, Error> {
+    let home = resolve_home()?;
+    let fpath = env::var_os("FPATH").unwrap_or_default();
+    Ok(env::split_paths(&fpath)
+        .find(|dir| dir.starts_with(&home))
+        .unwrap_or_else(|| home.join(".zsh").join("completions")))
+}
+
+/// Handles the `completion` command
+pub fn handle_completion(
+    shell: Shell,
+    install: bool,
+    cmd: &mut clap::Command,
+) -> Result<(), Error> {
+    if !install {
+        generate(shell, cmd, "suiup", &mut io::stdout());
+        return Ok(());
+    }
+
+    let path = completion_file(shell)?;
+    let dir = path.parent().expect("completion file has a parent folder");
+    fs::create_dir_all(dir).map_err(|e| {
+        anyhow!(
+            "Cannot create completion directory {}: {e}",
+            display_path(dir)
+        )
+    })?;
+    let mut file =
+        File::create(&path).map_err(|e| anyhow!("Cannot write {}: {e}", display_path(&path)))?;
+    generate(shell, cmd, "suiup", &mut file);
+
+    println!("Installed {shell} completions to {}", display_path(&path));
+    if shell == Shell::Zsh {
+        println!("Make sure this folder is in your fpath, e.g. in ~/.zshrc:");
+        println!("    fpath=({} $fpath)", display_path(dir));
+        println!("    autoload -U compinit; compinit");
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use indicatif::HumanBytes;
+
+use crate::paths::{binaries_dir, display_path, release_archive_dir};
+use crate::symbols::symbols;
+
+/// Free space below which suiup warns: a release archive and its extracted binaries take several
+/// hundred MB
+pub const LOW_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+/// Space available to the current user on the volume holding `path`, or on the one of its closest
+/// existing parent. `None` when it cannot be determined, e.g. on Windows.
+#[cfg(unix)]
+pub fn free_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = path.ancestors().find(|p| p.exists())?;
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is a valid C string and `stat` is only read after a successful call
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let stat = unsafe { stat.assume_init() };
+    #[allow(clippy::useless_conversion)]
+    Some(u64::from(stat.f_bavail) * u64::from(stat.f_frsize))
+}
+
+#[cfg(not(unix))]
+pub fn free_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// The folders releases are downloaded and extracted to, with their free space
+pub fn install_dirs_space() -> Vec<(&'static str, PathBuf, Option<u64>)> {
+    [
+        ("binaries", binaries_dir()),
+        ("release archives", release_archive_dir()),
+    ]
+    .into_iter()
+    .map(|(label, path)| {
+        let free = free_space(&path);
+        (label, path, free)
+    })
+    .collect()
+}
+
+/// Warns on stderr when a folder a release is downloaded or extracted to is short on space, so a
+/// full disk does not show up as a confusing failure halfway through the extraction
+pub fn warn_if_low_disk_space() {
+    let mut warned = vec![];
+    for (label, path, free) in install_dirs_space() {
+        let Some(free) = free.filter(|free| *free < LOW_DISK_SPACE) else {
+            continue;
+        };
+        // both folders usually live on the same volume
+        if warned.contains(&free) {
+            continue;
+        }
+        warned.push(free);
+        eprintln!(
+            "[{}] Only {} free for {label} at {}, the install may run out of space",
+            symbols().warn,
+            HumanBytes(free),
+            display_path(&path)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_free_space() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let free = free_space(temp_dir.path()).unwrap();
+        assert!(free > 0);
+        // a folder that does not exist yet is measured on its parent's volume
+        assert!(free_space(&temp_dir.path().join("not/created")).is_some());
+    }
+}
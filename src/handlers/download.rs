@@ -3,20 +3,30 @@
 
 use crate::handlers::release::{
     ensure_version_prefix, find_last_release_by_network, find_networks_with_version,
+    release_for_network,
 };
 use crate::handlers::version::extract_version_from_release;
-use crate::types::Repo;
+use crate::types::{Asset, Repo, NETWORKS};
 use crate::{handlers::release::release_list, paths::release_archive_dir, types::Release};
 use anyhow::{anyhow, bail, Error};
 use futures_util::StreamExt;
-use indicatif::{HumanBytes, ProgressBar, ProgressStyle};
+use indicatif::{HumanBytes, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use md5::Context;
+use regex::Regex;
 use reqwest::{
-    header::{HeaderMap, HeaderValue, USER_AGENT},
-    Client,
+    header::{HeaderMap, HeaderValue, ETAG, IF_NONE_MATCH, USER_AGENT},
+    Client, StatusCode,
 };
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fmt;
 use std::fs::File;
+use std::future::Future;
 use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
 use std::{cmp::min, io::Write, path::PathBuf, time::Instant};
 
 use tracing::debug;
@@ -97,23 +107,123 @@ fn generate_network_suggestions_error(
 
 /// Detects the current OS and architecture
 pub fn detect_os_arch() -> Result<(String, String), Error> {
+    let (os, arch) = host_target()?;
+    let (os, arch) = os_arch_tokens(os, arch)?;
+
+    println!("Detected: {os}-{arch}...");
+    Ok((os.to_string(), arch.to_string()))
+}
+
+/// The OS (`linux`, `windows`, `macos`) and Rust architecture suiup runs on
+fn host_target() -> Result<(&'static str, &'static str), Error> {
     let os = match whoami::platform() {
-        whoami::Platform::Linux => "ubuntu",
+        whoami::Platform::Linux => "linux",
         whoami::Platform::Windows => "windows",
         whoami::Platform::MacOS => "macos",
         _ => bail!("Unsupported OS. Supported only: Linux, Windows, MacOS"),
     };
-    let arch = match std::env::consts::ARCH {
+    Ok((os, std::env::consts::ARCH))
+}
+
+/// Fails with the list of supported targets if `repo` does not ship binaries for `os` and `arch`
+/// (as accepted by [`parse_target`], e.g. `linux` and `aarch64`)
+pub fn check_target(repo: Repo, os: &str, arch: &str) -> Result<(), Error> {
+    let supported = repo.supported_targets();
+    if supported.contains(&(os, arch)) {
+        return Ok(());
+    }
+    let targets: Vec<String> = supported
+        .iter()
+        .map(|(os, arch)| format!("{os}-{arch}"))
+        .collect();
+    bail!(
+        "{} is not available for {os}-{arch}. Supported targets: {}",
+        repo.binary_name(),
+        targets.join(", ")
+    )
+}
+
+/// Maps an OS (`linux`, `windows`, `macos`) and a Rust target architecture to the tokens used
+/// in the release asset names.
+pub fn os_arch_tokens(os: &str, arch: &str) -> Result<(&'static str, &'static str), Error> {
+    let os = match os {
+        "linux" => "ubuntu",
+        "windows" => "windows",
+        "macos" => "macos",
+        _ => bail!("Unsupported OS. Supported only: Linux, Windows, MacOS"),
+    };
+    let arch = match arch {
         "x86_64" => "x86_64",
-        "aarch64" if os == "macos" => "arm64",
+        "aarch64" if os == "macos" || os == "windows" => "arm64",
         "aarch64" => "aarch64",
         _ => bail!("Unsupported architecture. Supported only: x86_64, aarch64"),
     };
+    Ok((os, arch))
+}
 
-    println!("Detected: {os}-{arch}...");
+/// Parses a `--target` platform (e.g. `linux-x86_64` or `macos-aarch64`) into its canonical
+/// `<os>-<arch>` form, used to pick the release asset and name the folder binaries go to
+pub fn parse_target(target: &str) -> Result<String, Error> {
+    let (os, arch) = target.split_once('-').ok_or_else(|| {
+        anyhow!("Invalid target '{target}', expected <os>-<arch> (e.g. linux-x86_64)")
+    })?;
+    let arch = if arch == "arm64" { "aarch64" } else { arch };
+    os_arch_tokens(os, arch).map_err(|e| anyhow!("Invalid target '{target}': {e}"))?;
+    Ok(format!("{os}-{arch}"))
+}
+
+/// Release asset tokens of `repo` for `target` (as returned by [`parse_target`]), or for the
+/// host. Fails with the supported targets if `repo` has no binaries for it.
+pub fn target_os_arch(repo: Repo, target: Option<&str>) -> Result<(String, String), Error> {
+    let (os, arch) = match target {
+        Some(target) => target
+            .split_once('-')
+            .ok_or_else(|| anyhow!("Invalid target '{target}'"))?,
+        None => host_target()?,
+    };
+    check_target(repo, os, arch)?;
+    let (os, arch) = os_arch_tokens(os, arch)?;
+    if target.is_some() {
+        println!("Target: {os}-{arch}...");
+    } else {
+        println!("Detected: {os}-{arch}...");
+    }
     Ok((os.to_string(), arch.to_string()))
 }
 
+/// Number of attempts for network requests that may fail transiently
+pub const RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, doubled for every further one
+pub const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Runs `op` up to `attempts` times, waiting `delay` before the first retry and doubling it for
+/// every further one. Returns the last error if every attempt fails.
+pub async fn retry_with_backoff<T, F, Fut>(
+    attempts: u32,
+    delay: Duration,
+    mut op: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut delay = delay;
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                debug!("Attempt {attempt} of {attempts} failed: {e}, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Downloads a release with a specific version
 /// The network is used to filter the release
 pub async fn download_release_at_version(
@@ -121,8 +231,23 @@ pub async fn download_release_at_version(
     network: &str,
     version: &str,
     github_token: Option<String>,
+    checksum: Option<&Checksum>,
+    target: Option<&str>,
 ) -> Result<String, anyhow::Error> {
-    let (os, arch) = detect_os_arch()?;
+    let asset = find_asset_at_version(repo, network, version, github_token.clone(), target).await?;
+    download_asset(&asset, github_token, checksum).await
+}
+
+/// Finds the release asset of a specific version for the target (or this machine), without
+/// downloading it
+pub async fn find_asset_at_version(
+    repo: Repo,
+    network: &str,
+    version: &str,
+    github_token: Option<String>,
+    target: Option<&str>,
+) -> Result<Asset, anyhow::Error> {
+    let (os, arch) = target_os_arch(repo, target)?;
 
     // Ensure version has 'v' prefix for GitHub release tags
     let version = ensure_version_prefix(version);
@@ -139,7 +264,12 @@ pub async fn download_release_at_version(
         .iter()
         .find(|r| r.assets.iter().any(|a| a.name.contains(&tag)))
     {
-        download_asset_from_github(release, &os, &arch, github_token).await
+        select_asset(
+            &release_for_network(release, &tag),
+            repo.asset_naming(),
+            &os,
+            &arch,
+        )
     } else {
         headers.insert(USER_AGENT, HeaderValue::from_static("suiup"));
 
@@ -164,7 +294,12 @@ pub async fn download_release_at_version(
         }
 
         let release: Release = response.json().await?;
-        download_asset_from_github(&release, &os, &arch, github_token).await
+        select_asset(
+            &release_for_network(&release, &tag),
+            repo.asset_naming(),
+            &os,
+            &arch,
+        )
     }
 }
 
@@ -173,13 +308,28 @@ pub async fn download_latest_release(
     repo: Repo,
     network: &str,
     github_token: Option<String>,
+    checksum: Option<&Checksum>,
+    target: Option<&str>,
 ) -> Result<String, anyhow::Error> {
+    let asset = find_latest_asset(repo, network, github_token.clone(), target).await?;
+    download_asset(&asset, github_token, checksum).await
+}
+
+/// Finds the asset of the latest release for a given network and the target (or this machine),
+/// without downloading it
+pub async fn find_latest_asset(
+    repo: Repo,
+    network: &str,
+    github_token: Option<String>,
+    target: Option<&str>,
+) -> Result<Asset, anyhow::Error> {
+    // an unsupported target fails before GitHub is asked
+    let (os, arch) = target_os_arch(repo, target)?;
+
     println!("Downloading release list");
     debug!("Downloading release list for repo: {repo} and network: {network}");
     let releases = release_list(&repo, github_token.clone()).await?;
 
-    let (os, arch) = detect_os_arch()?;
-
     let last_release = find_last_release_by_network(releases.0.clone(), network)
         .await
         .ok_or_else(|| generate_network_suggestions_error(&repo, &releases.0, None, network))?;
@@ -189,16 +339,154 @@ pub async fn download_latest_release(
         extract_version_from_release(&last_release.assets[0].name)?
     );
 
-    download_asset_from_github(&last_release, &os, &arch, github_token).await
+    select_asset(&last_release, repo.asset_naming(), &os, &arch)
+}
+
+/// Expected digest of a downloaded file, given as `sha256:<hex>` or `md5:<hex>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    Sha256(String),
+    Md5(String),
 }
 
+/// Incremental digest with one of the [`Checksum`] algorithms, fed as the data comes in
+enum Hasher {
+    Sha256(Sha256),
+    Md5(Context),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Md5(context) => context.consume(data),
+        }
+    }
+
+    /// The digest of everything fed so far, as lowercase hex
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Md5(context) => format!("{:x}", context.finalize()),
+        }
+    }
+}
+
+/// Computes the digest of the file at `path` with `hasher`, reading it again from disk
+fn digest_file(path: &Path, mut hasher: Hasher) -> Result<String, Error> {
+    let mut file = File::open(path)?;
+    let mut buffer = [0u8; 8192];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher.finalize_hex())
+}
+
+impl Checksum {
+    /// A hasher with the same algorithm
+    fn hasher(&self) -> Hasher {
+        match self {
+            Checksum::Sha256(_) => Hasher::Sha256(Sha256::new()),
+            Checksum::Md5(_) => Hasher::Md5(Context::new()),
+        }
+    }
+
+    /// Checks the file at `path` against this checksum, deleting the file on mismatch so that a
+    /// corrupted or tampered download is never picked up from the cache
+    pub fn verify(&self, path: &Path, name: &str) -> Result<(), Error> {
+        let actual = digest_file(path, self.hasher())?;
+        self.check(&actual, path, name)
+    }
+
+    /// Checks the `actual` digest of the file at `path`, computed while it was downloaded or read
+    /// back from disk, deleting the file on mismatch
+    fn check(&self, actual: &str, path: &Path, name: &str) -> Result<(), Error> {
+        let (algorithm, expected) = match self {
+            Checksum::Sha256(hex) => ("sha256", hex),
+            Checksum::Md5(hex) => ("md5", hex),
+        };
+        if actual != expected {
+            std::fs::remove_file(path)?;
+            bail!("Checksum mismatch for {name}: expected {self}, got {algorithm}:{actual}. The downloaded file was deleted");
+        }
+        println!("Checksum verified for {name}");
+        Ok(())
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, hex) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Checksum must be given as sha256:<hex> or md5:<hex>"))?;
+        let hex = hex.trim().to_lowercase();
+        let (checksum, len) = match algorithm.to_lowercase().as_str() {
+            "sha256" => (Checksum::Sha256(hex.clone()), 64),
+            "md5" => (Checksum::Md5(hex.clone()), 32),
+            _ => bail!("Unsupported checksum algorithm '{algorithm}', use sha256 or md5"),
+        };
+        if hex.len() != len || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Invalid {algorithm} checksum '{hex}', expected {len} hex characters");
+        }
+        Ok(checksum)
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Checksum::Sha256(hex) => write!(f, "sha256:{hex}"),
+            Checksum::Md5(hex) => write!(f, "md5:{hex}"),
+        }
+    }
+}
+
+/// Downloads `url` to `download_to`, reusing a cached copy when possible. When `checksum` is
+/// given, the file (downloaded or cached) must match it.
 pub async fn download_file(
     url: &str,
     download_to: &PathBuf,
     name: &str,
     github_token: Option<String>,
+    checksum: Option<&Checksum>,
 ) -> Result<String, Error> {
+    let (name, digest) = fetch_file(url, download_to, name, github_token, checksum).await?;
+    match (checksum, digest) {
+        (Some(checksum), Some(digest)) => checksum.check(&digest, download_to, &name)?,
+        // a file found in the cache was not streamed, so it is read again
+        (Some(checksum), None) => checksum.verify(download_to, &name)?,
+        (None, _) => {}
+    }
+    Ok(name)
+}
+
+/// Path of a file kept next to `file` (its checksum or ETag), named after the whole file name:
+/// `with_extension` would replace the end of a version, so `mvr-v0.0.8` and `mvr-v0.0.9` would
+/// share `mvr-v0.0.etag`.
+pub fn sidecar_path(file: &Path, extension: &str) -> PathBuf {
+    let mut name = file.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{extension}"));
+    file.with_file_name(name)
+}
+
+/// Downloads `url` to `download_to` unless the cached file is still valid. Returns the file name
+/// and, when it was downloaded, its digest with the algorithm of `checksum`, computed as the data
+/// came in.
+async fn fetch_file(
+    url: &str,
+    download_to: &PathBuf,
+    name: &str,
+    github_token: Option<String>,
+    checksum: Option<&Checksum>,
+) -> Result<(String, Option<String>), Error> {
     let client = Client::new();
+    debug!("Downloading {url} to {}", download_to.display());
 
     // Start with a basic request
     let mut request = client.get(url).header("User-Agent", "suiup");
@@ -210,8 +498,30 @@ pub async fn download_file(
         }
     }
 
+    // Files downloaded to a fixed name (e.g. the walrus binary on Google Storage) would otherwise
+    // be fetched again every time, so revalidate them with the ETag stored next to the file
+    let etag_path = sidecar_path(download_to, "etag");
+    if download_to.exists() {
+        if let Ok(etag) = std::fs::read_to_string(&etag_path) {
+            debug!("Revalidating cached {name} with ETag {}", etag.trim());
+            request = request.header(IF_NONE_MATCH, etag.trim());
+        }
+    }
+
     let response = request.send().await?;
 
+    if response.status() == StatusCode::NOT_MODIFIED {
+        debug!("Cache hit for {name}: server reports it not modified");
+        println!("Found {name} in cache, not modified since the last download");
+        return Ok((name.to_string(), None));
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|e| e.to_str().ok())
+        .map(str::to_string);
+
     let response = response.error_for_status();
 
     if let Err(ref e) = response {
@@ -236,35 +546,34 @@ pub async fn download_file(
     }
 
     if download_to.exists() {
-        if download_to.metadata()?.len() == total_size {
+        let local_size = download_to.metadata()?.len();
+        if local_size == total_size {
             // Check md5 if .md5 file exists
-            let md5_path = download_to.with_extension("md5");
+            let md5_path = sidecar_path(download_to, "md5");
             if md5_path.exists() {
-                let mut file = File::open(download_to)?;
-                let mut hasher = Context::new();
-                let mut buffer = [0u8; 8192];
-                loop {
-                    let n = file.read(&mut buffer)?;
-                    if n == 0 {
-                        break;
-                    }
-                    hasher.consume(&buffer[..n]);
-                }
-                let result = hasher.finalize();
-                let local_md5 = format!("{:x}", result);
+                let local_md5 = digest_file(download_to, Hasher::Md5(Context::new()))?;
                 let expected_md5 = std::fs::read_to_string(md5_path)?.trim().to_string();
                 if local_md5 == expected_md5 {
+                    debug!("Cache hit for {name}: size and md5 {local_md5} match");
                     println!("Found {name} in cache, md5 verified");
-                    return Ok(name.to_string());
+                    return Ok((name.to_string(), None));
                 } else {
-                    println!("MD5 mismatch for {name}, re-downloading...");
+                    debug!(
+                        "Cache miss for {name}: md5 mismatch, expected {expected_md5}, got {local_md5}"
+                    );
+                    eprintln!("MD5 mismatch for {name}, re-downloading...");
                 }
             } else {
+                debug!("Cache hit for {name}: size {local_size} matches, no md5 to check");
                 println!("Found {name} in cache (no md5 to check)");
-                return Ok(name.to_string());
+                return Ok((name.to_string(), None));
             }
+        } else {
+            debug!("Cache miss for {name}: size mismatch, cached {local_size} bytes, remote {total_size} bytes");
         }
         std::fs::remove_file(download_to)?;
+    } else {
+        debug!("Cache miss for {name}: not downloaded yet");
     }
 
     let pb = ProgressBar::new(total_size);
@@ -272,43 +581,55 @@ pub async fn download_file(
         .template("Downloading release: {spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
         .unwrap()
         .progress_chars("=>-"));
+    let quiet_progress = quiet_progress();
+    if quiet_progress {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    let mut last_line: Option<Instant> = None;
 
     let mut file = std::fs::File::create(download_to)?;
     let mut downloaded: u64 = 0;
+    let mut received: u64 = 0;
     let mut stream = response.bytes_stream();
-    let start = Instant::now();
+    // The ETA is computed by indicatif from the position updates, the speed message uses a
+    // sliding window so it reflects the current rate rather than the average since start.
+    let mut speed = SpeedWindow::new(SPEED_WINDOW);
+    // digests are computed as chunks arrive, so checking them does not read the file again
+    let md5_path = sidecar_path(download_to, "md5");
+    let mut md5 = md5_path.exists().then(|| Hasher::Md5(Context::new()));
+    let mut digest = checksum.map(Checksum::hasher);
 
     while let Some(item) = stream.next().await {
         let chunk = item?;
         file.write_all(&chunk)?;
+        for hasher in [&mut md5, &mut digest].into_iter().flatten() {
+            hasher.update(&chunk);
+        }
+        received += chunk.len() as u64;
         let new = min(downloaded + (chunk.len() as u64), total_size);
         downloaded = new;
         pb.set_position(new);
 
-        let elapsed = start.elapsed().as_secs_f64();
-        if elapsed > 0.0 {
-            let speed = downloaded as f64 / elapsed;
-            pb.set_message(format!("Speed: {}/s", HumanBytes(speed as u64)));
+        let now = Instant::now();
+        if let Some(rate) = speed.record(now, received) {
+            pb.set_message(format!("Speed: {}/s", HumanBytes(rate as u64)));
+        }
+        if quiet_progress
+            && last_line.is_none_or(|last| now.duration_since(last) >= PROGRESS_LINE_INTERVAL)
+        {
+            println!("{}", progress_line(downloaded, total_size));
+            last_line = Some(now);
         }
     }
 
     pb.finish_with_message("Download complete");
+    if quiet_progress {
+        println!("{}", progress_line(downloaded, total_size));
+    }
 
     // After download, check md5 if .md5 file exists
-    let md5_path = download_to.with_extension("md5");
-    if md5_path.exists() {
-        let mut file = File::open(download_to)?;
-        let mut hasher = Context::new();
-        let mut buffer = [0u8; 8192];
-        loop {
-            let n = file.read(&mut buffer)?;
-            if n == 0 {
-                break;
-            }
-            hasher.consume(&buffer[..n]);
-        }
-        let result = hasher.finalize();
-        let local_md5 = format!("{:x}", result);
+    if let Some(md5) = md5 {
+        let local_md5 = md5.finalize_hex();
         let expected_md5 = std::fs::read_to_string(md5_path)?.trim().to_string();
         if local_md5 != expected_md5 {
             return Err(anyhow!(format!(
@@ -320,31 +641,317 @@ pub async fn download_file(
         }
     }
 
-    Ok(name.to_string())
+    match etag {
+        Some(etag) => std::fs::write(&etag_path, etag)?,
+        None if etag_path.exists() => std::fs::remove_file(&etag_path)?,
+        None => {}
+    }
+
+    debug!(
+        "Downloaded {received} bytes of {name} to {}",
+        download_to.display()
+    );
+    Ok((name.to_string(), digest.map(Hasher::finalize_hex)))
+}
+
+/// Time span over which the download speed is averaged
+const SPEED_WINDOW: Duration = Duration::from_secs(2);
+
+static QUIET_PROGRESS: OnceLock<bool> = OnceLock::new();
+
+/// Minimum time between two progress lines in quiet progress mode
+const PROGRESS_LINE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Replaces the download progress bar with plain progress lines, which read better in logs and
+/// in terminals that render carriage returns poorly. Only the first call has an effect.
+pub fn set_quiet_progress(quiet: bool) {
+    let _ = QUIET_PROGRESS.set(quiet);
+}
+
+fn quiet_progress() -> bool {
+    QUIET_PROGRESS.get().copied().unwrap_or(false)
+}
+
+/// Progress line printed in quiet progress mode, e.g. `Downloaded 50% (106/212 MB)`. A `total`
+/// of zero means the size is unknown.
+fn progress_line(downloaded: u64, total: u64) -> String {
+    const MB: u64 = 1_000_000;
+    match (downloaded * 100).checked_div(total) {
+        Some(percent) => format!(
+            "Downloaded {percent}% ({}/{} MB)",
+            downloaded / MB,
+            total / MB
+        ),
+        None => format!("Downloaded {} MB", downloaded / MB),
+    }
+}
+
+/// Computes the download rate over a sliding time window
+struct SpeedWindow {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl SpeedWindow {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records the total number of bytes received so far and returns the rate in bytes per second
+    /// over the window, or `None` if not enough time has passed to compute it.
+    fn record(&mut self, now: Instant, total: u64) -> Option<f64> {
+        self.samples.push_back((now, total));
+        // keep the newest sample that is at least `window` old as the reference point
+        while self.samples.len() > 1 && now.duration_since(self.samples[1].0) >= self.window {
+            self.samples.pop_front();
+        }
+
+        let (since, bytes) = *self.samples.front()?;
+        let elapsed = now.duration_since(since).as_secs_f64();
+        if elapsed > 0.0 {
+            Some(total.saturating_sub(bytes) as f64 / elapsed)
+        } else {
+            None
+        }
+    }
+}
+
+/// How a repository names the release asset built for a platform. Adding a new binary is a
+/// matter of picking (or adding) its scheme in [`Repo::asset_naming`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetNaming {
+    /// One archive per network and platform: `<binary>-<network>-v<version>-<os>-<arch>.tgz`
+    NetworkArchive(&'static str),
+    /// A bare binary per platform, the same for every network: `<binary>-<os>-<arch>[.exe]`
+    Binary(&'static str),
+}
+
+impl Repo {
+    /// The naming scheme of this repository's release assets
+    pub fn asset_naming(&self) -> AssetNaming {
+        match self {
+            Repo::Sui | Repo::Walrus | Repo::WalrusSites => {
+                AssetNaming::NetworkArchive(self.binary_name())
+            }
+            Repo::Mvr => AssetNaming::Binary(self.binary_name()),
+        }
+    }
+
+    /// The networks this repository publishes release archives for, as they appear in the
+    /// `<binary>-<network>-v<version>` asset names; empty for standalone binaries
+    pub fn release_networks(&self) -> &'static [&'static str] {
+        match self {
+            Repo::Sui | Repo::Walrus => &NETWORKS,
+            Repo::WalrusSites => &["mainnet"],
+            Repo::Mvr => &[],
+        }
+    }
+
+    /// The (os, arch) pairs this repository publishes binaries for
+    pub fn supported_targets(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Repo::Sui | Repo::Mvr => &[
+                ("linux", "x86_64"),
+                ("linux", "aarch64"),
+                ("macos", "x86_64"),
+                ("macos", "aarch64"),
+                ("windows", "x86_64"),
+                ("windows", "aarch64"),
+            ],
+            Repo::Walrus => &[
+                ("linux", "x86_64"),
+                ("linux", "aarch64"),
+                ("macos", "x86_64"),
+                ("macos", "aarch64"),
+                ("windows", "x86_64"),
+            ],
+            Repo::WalrusSites => &[
+                ("linux", "x86_64"),
+                ("macos", "x86_64"),
+                ("macos", "aarch64"),
+                ("windows", "x86_64"),
+            ],
+        }
+    }
+}
+
+impl AssetNaming {
+    /// Builds the expected asset name for the given release asset tokens (as returned by
+    /// [`os_arch_tokens`]), network and version
+    pub fn asset_name(&self, os: &str, arch: &str, network: &str, version: &str) -> String {
+        match self {
+            AssetNaming::NetworkArchive(binary) => {
+                let version = ensure_version_prefix(version);
+                format!("{binary}-{network}-{version}-{os}-{arch}.tgz")
+            }
+            AssetNaming::Binary(binary) if os == "windows" => format!("{binary}-{os}-{arch}.exe"),
+            AssetNaming::Binary(binary) => format!("{binary}-{os}-{arch}"),
+        }
+    }
+
+    /// Finds the asset for `os` and `arch` among the asset names of a release, accepting the
+    /// aliases described in [`find_asset_name`]
+    pub fn find<'a>(&self, names: &[&'a str], os: &str, arch: &str) -> Option<&'a str> {
+        match self {
+            AssetNaming::NetworkArchive(_) => find_asset_name(names, os, arch),
+            AssetNaming::Binary(binary) => {
+                let prefix = format!("{binary}-");
+                let names: Vec<&str> = names
+                    .iter()
+                    .copied()
+                    .filter(|name| name.starts_with(&prefix))
+                    .filter(|name| (os == "windows") == name.ends_with(".exe"))
+                    .collect();
+                find_asset_name(&names, os, arch)
+            }
+        }
+    }
 }
 
-/// Downloads the archived release from GitHub and returns the file name
-/// The `network, os, and arch` parameters are used to retrieve the correct release for the target
+static ASSET_PATTERN: OnceLock<Regex> = OnceLock::new();
+
+/// Selects release assets by matching their names against `pattern` instead of by OS and
+/// architecture, for unusual asset names. Only the first call has an effect.
+pub fn set_asset_pattern(pattern: Regex) {
+    let _ = ASSET_PATTERN.set(pattern);
+}
+
+/// The name matching the `--asset-pattern` regex among `names`, when one was given. Fails unless
+/// exactly one name matches.
+pub fn match_asset_pattern<'a>(names: &[&'a str]) -> Option<Result<&'a str, Error>> {
+    ASSET_PATTERN
+        .get()
+        .map(|pattern| find_by_pattern(names, pattern))
+}
+
+fn find_by_pattern<'a>(names: &[&'a str], pattern: &Regex) -> Result<&'a str, Error> {
+    let matching: Vec<&str> = names
+        .iter()
+        .copied()
+        .filter(|name| pattern.is_match(name))
+        .collect();
+    match matching.as_slice() {
+        [name] => Ok(name),
+        [] => bail!(
+            "No release asset matches --asset-pattern '{pattern}'. Assets: {}",
+            names.join(", ")
+        ),
+        _ => bail!(
+            "--asset-pattern '{pattern}' matches several assets: {}. Make it more specific",
+            matching.join(", ")
+        ),
+    }
+}
+
+/// Selects the asset of the release to download
+/// The `naming, os, and arch` parameters are used to retrieve the correct release for the target
 /// architecture and OS
-async fn download_asset_from_github(
+fn select_asset(
     release: &Release,
+    naming: AssetNaming,
     os: &str,
     arch: &str,
-    github_token: Option<String>,
-) -> Result<String, anyhow::Error> {
-    let asset = release
-        .assets
-        .iter()
-        .find(|&a| a.name.contains(arch) && a.name.contains(os.to_string().to_lowercase().as_str()))
+) -> Result<Asset, anyhow::Error> {
+    let names: Vec<&str> = release.assets.iter().map(|a| a.name.as_str()).collect();
+    let name = match match_asset_pattern(&names) {
+        Some(name) => Some(name?),
+        None => naming.find(&names, os, arch),
+    };
+    let asset = name
+        .and_then(|name| release.assets.iter().find(|a| a.name == name))
         .ok_or_else(|| anyhow!("Asset not found for {os}-{arch}"))?;
+    debug!(
+        "Matched {} for os {os} and arch {arch} among {} assets ({naming:?})",
+        asset.name,
+        names.len()
+    );
+    println!("Selected asset: {}", asset.name);
+    Ok(asset.clone())
+}
 
+/// Downloads the archived release asset from GitHub and returns the file name
+async fn download_asset(
+    asset: &Asset,
+    github_token: Option<String>,
+    checksum: Option<&Checksum>,
+) -> Result<String, anyhow::Error> {
     let url = asset.clone().browser_download_url;
     let name = asset.clone().name;
     let path = release_archive_dir();
     let mut file_path = path.clone();
     file_path.push(&asset.name);
 
-    download_file(&url, &file_path, &name, github_token).await
+    download_file(&url, &file_path, &name, github_token, checksum).await
+}
+
+/// Returns the names an OS goes by in release asset names, the one used by Sui releases first
+fn os_aliases(os: &str) -> Vec<String> {
+    let os = os.to_lowercase();
+    match os.as_str() {
+        "ubuntu" | "linux" => vec!["ubuntu".to_string(), "linux".to_string()],
+        "macos" | "darwin" | "apple" => {
+            vec![
+                "macos".to_string(),
+                "darwin".to_string(),
+                "apple".to_string(),
+            ]
+        }
+        _ => vec![os],
+    }
+}
+
+/// Finds the release asset name for the given OS and architecture tokens. Releases do not name
+/// platforms consistently, so any alias of the OS (e.g. `ubuntu` or `linux`) and of the
+/// architecture (`arm64` or `aarch64`) is accepted.
+///
+/// An asset named with the exact `<os>-<arch>` token (e.g. `macos-arm64`) is preferred, then any
+/// asset mentioning both the OS and the architecture, and for macOS a universal build is only used
+/// when there is no architecture specific one.
+fn find_asset_name<'a>(names: &[&'a str], os: &str, arch: &str) -> Option<&'a str> {
+    let os_aliases = os_aliases(os);
+    let arch_aliases: &[&str] = match arch {
+        "arm64" | "aarch64" => &["arm64", "aarch64"],
+        _ => &[arch],
+    };
+    let find = |matches: &dyn Fn(&str) -> bool| {
+        names
+            .iter()
+            .copied()
+            .find(|name| matches(&name.to_lowercase()))
+    };
+    let has_os = |name: &str| os_aliases.iter().any(|os| name.contains(os.as_str()));
+
+    // prefer the exact tokens, in alias order, before falling back to a looser match
+    os_aliases
+        .iter()
+        .flat_map(|os| arch_aliases.iter().map(move |arch| format!("{os}-{arch}")))
+        .find_map(|token| find(&|name| has_token(name, &token)))
+        .or_else(|| {
+            arch_aliases.iter().find_map(|arch| {
+                find(&|name| has_os(name) && name.contains(arch) && !name.contains("universal"))
+            })
+        })
+        .or_else(|| {
+            if os_aliases[0] == "macos" {
+                find(&|name| has_os(name) && name.contains("universal"))
+            } else {
+                None
+            }
+        })
+}
+
+/// Checks that `token` appears in `name` as a whole, i.e. it is not followed by more characters of
+/// the same word (`macos-arm64` matches `macos-arm64.tgz` but not `macos-arm64e.tgz`).
+fn has_token(name: &str, token: &str) -> bool {
+    name.match_indices(token).any(|(idx, _)| {
+        name[idx + token.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_ascii_alphanumeric())
+    })
 }
 
 #[cfg(test)]
@@ -352,6 +959,24 @@ mod tests {
     use super::*;
     use crate::types::{Asset, Release};
 
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            sidecar_path(Path::new("/cache/mvr-v0.0.8"), "etag"),
+            Path::new("/cache/mvr-v0.0.8.etag")
+        );
+        assert_eq!(
+            sidecar_path(Path::new("sui-testnet-v1.40.1-ubuntu-x86_64.tgz"), "md5"),
+            Path::new("sui-testnet-v1.40.1-ubuntu-x86_64.tgz.md5")
+        );
+    }
+
+    fn find_asset<'a>(release: &'a Release, os: &str, arch: &str) -> Option<&'a Asset> {
+        let names: Vec<&str> = release.assets.iter().map(|a| a.name.as_str()).collect();
+        let name = find_asset_name(&names, os, arch)?;
+        release.assets.iter().find(|a| a.name == name)
+    }
+
     fn create_test_release(asset_names: Vec<&str>) -> Release {
         Release {
             assets: asset_names
@@ -361,7 +986,280 @@ mod tests {
                     browser_download_url: format!("https://example.com/{}", name),
                 })
                 .collect(),
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn test_speed_window_uses_recent_samples() {
+        let start = Instant::now();
+        let mut speed = SpeedWindow::new(Duration::from_secs(2));
+
+        assert_eq!(speed.record(start, 0), None);
+        // slow start: 1 KB/s for 10 seconds
+        for i in 1..=10 {
+            speed.record(start + Duration::from_secs(i), i * 1024);
         }
+        // then fast: 100 KB/s for 2 seconds
+        speed.record(start + Duration::from_secs(11), 10 * 1024 + 100 * 1024);
+        let rate = speed
+            .record(start + Duration::from_secs(12), 10 * 1024 + 200 * 1024)
+            .unwrap();
+
+        // only the last 2 seconds count, not the cumulative average
+        assert_eq!(rate, 100.0 * 1024.0);
+    }
+
+    #[test]
+    fn test_os_arch_tokens() {
+        assert_eq!(
+            os_arch_tokens("linux", "x86_64").unwrap(),
+            ("ubuntu", "x86_64")
+        );
+        assert_eq!(
+            os_arch_tokens("linux", "aarch64").unwrap(),
+            ("ubuntu", "aarch64")
+        );
+        assert_eq!(
+            os_arch_tokens("macos", "aarch64").unwrap(),
+            ("macos", "arm64")
+        );
+        assert_eq!(
+            os_arch_tokens("windows", "aarch64").unwrap(),
+            ("windows", "arm64")
+        );
+        assert!(os_arch_tokens("windows", "riscv64").is_err());
+        assert!(os_arch_tokens("freebsd", "x86_64").is_err());
+    }
+
+    #[test]
+    fn test_progress_line() {
+        assert_eq!(
+            progress_line(106_000_000, 212_000_000),
+            "Downloaded 50% (106/212 MB)"
+        );
+        assert_eq!(
+            progress_line(212_000_000, 212_000_000),
+            "Downloaded 100% (212/212 MB)"
+        );
+        assert_eq!(progress_line(5_500_000, 0), "Downloaded 5 MB");
+    }
+
+    #[test]
+    fn test_check_target() {
+        assert!(check_target(Repo::Sui, "linux", "aarch64").is_ok());
+        assert!(check_target(Repo::Mvr, "macos", "aarch64").is_ok());
+
+        let err = check_target(Repo::Walrus, "windows", "aarch64").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "walrus is not available for windows-aarch64. Supported targets: linux-x86_64, \
+             linux-aarch64, macos-x86_64, macos-aarch64, windows-x86_64"
+        );
+        assert!(check_target(Repo::Walrus, "linux", "aarch64").is_ok());
+        assert!(check_target(Repo::Sui, "windows", "aarch64").is_ok());
+        assert!(check_target(Repo::Mvr, "windows", "aarch64").is_ok());
+        assert!(check_target(Repo::Sui, "linux", "riscv64").is_err());
+
+        // every supported target maps to release asset tokens
+        for repo in [Repo::Sui, Repo::Mvr, Repo::Walrus, Repo::WalrusSites] {
+            for (os, arch) in repo.supported_targets() {
+                assert!(os_arch_tokens(os, arch).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_by_pattern() {
+        let names = [
+            "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+            "sui-testnet-v1.40.1-linux-x86_64-musl.tgz",
+            "sui-testnet-v1.40.1-macos-arm64.tgz",
+        ];
+        let pattern = |p| Regex::new(p).unwrap();
+
+        assert_eq!(
+            find_by_pattern(&names, &pattern("linux.*musl")).unwrap(),
+            "sui-testnet-v1.40.1-linux-x86_64-musl.tgz"
+        );
+
+        let err = find_by_pattern(&names, &pattern("windows")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("No release asset matches --asset-pattern 'windows'"));
+
+        let err = find_by_pattern(&names, &pattern("x86_64")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--asset-pattern 'x86_64' matches several assets: sui-testnet-v1.40.1-ubuntu-x86_64.tgz, \
+             sui-testnet-v1.40.1-linux-x86_64-musl.tgz. Make it more specific"
+        );
+    }
+
+    #[test]
+    fn test_asset_naming_network_archives() {
+        for repo in [Repo::Sui, Repo::Walrus, Repo::WalrusSites] {
+            let naming = repo.asset_naming();
+            let binary = repo.binary_name();
+            assert_eq!(naming, AssetNaming::NetworkArchive(binary));
+            assert_eq!(
+                naming.asset_name("ubuntu", "x86_64", "testnet", "1.40.1"),
+                format!("{binary}-testnet-v1.40.1-ubuntu-x86_64.tgz")
+            );
+
+            let linux = naming.asset_name("ubuntu", "x86_64", "testnet", "v1.40.1");
+            let macos = naming.asset_name("macos", "arm64", "testnet", "v1.40.1");
+            let names = [linux.as_str(), macos.as_str()];
+            assert_eq!(
+                naming.find(&names, "ubuntu", "x86_64"),
+                Some(linux.as_str())
+            );
+            assert_eq!(naming.find(&names, "macos", "arm64"), Some(macos.as_str()));
+            assert_eq!(naming.find(&names, "windows", "x86_64"), None);
+        }
+    }
+
+    #[test]
+    fn test_asset_naming_mvr() {
+        let naming = Repo::Mvr.asset_naming();
+        assert_eq!(naming, AssetNaming::Binary("mvr"));
+        assert_eq!(
+            naming.asset_name("ubuntu", "aarch64", "standalone", "v0.0.8"),
+            "mvr-ubuntu-aarch64"
+        );
+        assert_eq!(
+            naming.asset_name("windows", "x86_64", "standalone", "v0.0.8"),
+            "mvr-windows-x86_64.exe"
+        );
+
+        let names = [
+            "mvr-ubuntu-x86_64",
+            "mvr-ubuntu-aarch64",
+            "mvr-macos-arm64",
+            "mvr-windows-x86_64.exe",
+            "mvr-windows-x86_64.exe.sha256",
+            "checksums-ubuntu-x86_64.txt",
+        ];
+        assert_eq!(
+            naming.find(&names, "ubuntu", "x86_64"),
+            Some("mvr-ubuntu-x86_64")
+        );
+        assert_eq!(
+            naming.find(&names, "ubuntu", "aarch64"),
+            Some("mvr-ubuntu-aarch64")
+        );
+        assert_eq!(
+            naming.find(&names, "macos", "arm64"),
+            Some("mvr-macos-arm64")
+        );
+        assert_eq!(
+            naming.find(&names, "windows", "x86_64"),
+            Some("mvr-windows-x86_64.exe")
+        );
+        assert_eq!(naming.find(&names, "windows", "arm64"), None);
+    }
+
+    #[test]
+    fn test_find_asset_windows_arm64() {
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-ubuntu-aarch64.tgz",
+            "sui-testnet-v1.40.1-windows-x86_64.tgz",
+            "sui-testnet-v1.40.1-windows-arm64.tgz",
+        ]);
+        let (os, arch) = os_arch_tokens("windows", "aarch64").unwrap();
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-windows-arm64.tgz"
+        );
+
+        // releases that name the windows arm build `aarch64` are still picked up
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-windows-x86_64.tgz",
+            "sui-testnet-v1.40.1-windows-aarch64.tgz",
+        ]);
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-windows-aarch64.tgz"
+        );
+
+        let release = create_test_release(vec!["sui-testnet-v1.40.1-windows-x86_64.tgz"]);
+        assert!(find_asset(&release, os, arch).is_none());
+    }
+
+    #[test]
+    fn test_find_asset_macos_prefers_arch_over_universal() {
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-macos-universal.tgz",
+            "sui-testnet-v1.40.1-macos-arm64.tgz",
+            "sui-testnet-v1.40.1-macos-x86_64.tgz",
+        ]);
+        let (os, arch) = os_arch_tokens("macos", "aarch64").unwrap();
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-macos-arm64.tgz"
+        );
+        let (os, arch) = os_arch_tokens("macos", "x86_64").unwrap();
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-macos-x86_64.tgz"
+        );
+
+        // the universal build is only a fallback
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-macos-universal.tgz",
+            "sui-testnet-v1.40.1-ubuntu-aarch64.tgz",
+        ]);
+        let (os, arch) = os_arch_tokens("macos", "aarch64").unwrap();
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-macos-universal.tgz"
+        );
+    }
+
+    #[test]
+    fn test_find_asset_os_aliases() {
+        let (os, arch) = os_arch_tokens("linux", "x86_64").unwrap();
+
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-macos-x86_64.tgz",
+            "sui-testnet-v1.40.1-linux-x86_64.tgz",
+        ]);
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-linux-x86_64.tgz"
+        );
+
+        // `ubuntu` is preferred when both names are present
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-linux-x86_64.tgz",
+            "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+        ]);
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "sui-testnet-v1.40.1-ubuntu-x86_64.tgz"
+        );
+
+        let (os, arch) = os_arch_tokens("macos", "aarch64").unwrap();
+        let release = create_test_release(vec![
+            "walrus-testnet-v1.18.2-linux-arm64.tgz",
+            "walrus-testnet-v1.18.2-darwin-arm64.tgz",
+        ]);
+        assert_eq!(
+            find_asset(&release, os, arch).unwrap().name,
+            "walrus-testnet-v1.18.2-darwin-arm64.tgz"
+        );
+    }
+
+    #[test]
+    fn test_find_asset_exact_token() {
+        let release = create_test_release(vec![
+            "sui-testnet-v1.40.1-macos-arm64e.tgz",
+            "sui-testnet-v1.40.1-macos-arm64.tgz",
+        ]);
+        assert_eq!(
+            find_asset(&release, "macos", "arm64").unwrap().name,
+            "sui-testnet-v1.40.1-macos-arm64.tgz"
+        );
     }
 
     #[test]
@@ -426,4 +1324,99 @@ mod tests {
         assert!(error_msg.contains("mvr is a standalone binary"));
         assert!(error_msg.contains("suiup install mvr"));
     }
+
+    #[test]
+    fn test_checksum_parse() {
+        let sha = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(
+            format!("sha256:{sha}").parse::<Checksum>().unwrap(),
+            Checksum::Sha256(sha.to_lowercase())
+        );
+        assert_eq!(
+            "md5:900150983cd24fb0d6963f7d28e17f72"
+                .parse::<Checksum>()
+                .unwrap()
+                .to_string(),
+            "md5:900150983cd24fb0d6963f7d28e17f72"
+        );
+
+        assert!(sha.parse::<Checksum>().is_err());
+        assert!("sha1:a9993e364706816aba3e25717850c26c9cd0d89d"
+            .parse::<Checksum>()
+            .is_err());
+        assert!("sha256:abc".parse::<Checksum>().is_err());
+        assert!("md5:zz0150983cd24fb0d6963f7d28e17f72"
+            .parse::<Checksum>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_checksum_verify_deletes_on_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive.tgz");
+        std::fs::write(&path, "abc").unwrap();
+
+        let sha: Checksum =
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+                .parse()
+                .unwrap();
+        let md5: Checksum = "md5:900150983cd24fb0d6963f7d28e17f72".parse().unwrap();
+        assert!(sha.verify(&path, "archive.tgz").is_ok());
+        assert!(md5.verify(&path, "archive.tgz").is_ok());
+
+        let wrong: Checksum = "md5:00000000000000000000000000000000".parse().unwrap();
+        let err = wrong.verify(&path, "archive.tgz").unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_streamed_digest_matches_file_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive.tgz");
+        let data = b"The quick brown fox jumps over the lazy dog";
+        std::fs::write(&path, data).unwrap();
+
+        let sha: Checksum =
+            "sha256:d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+                .parse()
+                .unwrap();
+        let md5: Checksum = "md5:9e107d9d372bb6826bd81d3542a419d6".parse().unwrap();
+        for checksum in [sha, md5] {
+            // fed in uneven chunks, as they come from the network
+            let mut hasher = checksum.hasher();
+            for chunk in data.chunks(7) {
+                hasher.update(chunk);
+            }
+            let streamed = hasher.finalize_hex();
+            assert_eq!(streamed, digest_file(&path, checksum.hasher()).unwrap());
+            assert!(checksum.check(&streamed, &path, "archive.tgz").is_ok());
+        }
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = retry_with_backoff(3, Duration::ZERO, || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                bail!("transient")
+            }
+            Ok("releases")
+        })
+        .await;
+        assert_eq!(result.unwrap(), "releases");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), Error> = retry_with_backoff(3, Duration::ZERO, || async {
+            let attempt = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            bail!("attempt {attempt} failed")
+        })
+        .await;
+        assert_eq!(result.unwrap_err().to_string(), "attempt 3 failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 }
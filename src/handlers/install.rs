@@ -1,23 +1,109 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use super::check_if_binaries_exist;
 use super::version::extract_version_from_release;
-use crate::commands::BinaryName;
-use crate::handlers::download::{download_latest_release, download_release_at_version};
-use crate::handlers::{extract_component, update_after_install};
-use crate::paths::binaries_dir;
+use crate::commands::{parse_component_with_version, BinaryName};
+use crate::handlers::download::{
+    download_latest_release, download_release_at_version, find_asset_at_version, find_latest_asset,
+    parse_target, Checksum,
+};
+use crate::handlers::release::{ensure_version_prefix, release_list, release_published_at};
+use crate::handlers::{
+    available_components, extract_component, install_alias, installed_binary_path,
+    place_companions, release_binary_path, update_after_install,
+};
+use crate::paths::{binaries_dir, default_file_path, display_path, get_default_bin_dir};
 use crate::standalone;
-use crate::types::{BinaryVersion, InstalledBinaries, Repo};
+use crate::types::{BinaryVersion, Config, InstalledBinaries, NetworkOrBranch, Repo, Version};
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Error;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
 
+/// What an install did, so callers using suiup as a library can tell a new install from one that
+/// was already present. The CLI prints its [`InstallOutcome::summary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstallOutcome {
+    pub binary: String,
+    pub version: String,
+    /// The network, `standalone` release or branch the binary was installed from
+    pub network: String,
+    /// Whether this install downloaded or built the binary, rather than finding it present
+    pub newly_installed: bool,
+    /// Whether the binary was made the default one
+    pub set_default: bool,
+    /// Where the versioned binary is stored
+    pub path: PathBuf,
+    /// The copy in the default bin folder, when one was made
+    pub default_path: Option<PathBuf>,
+    /// Custom name the default copy was made under
+    pub alias: Option<String>,
+    /// Platform the binary was downloaded for with `--target`
+    pub target: Option<String>,
+}
+
+impl InstallOutcome {
+    /// An outcome for a binary that was already installed and left as it was
+    fn present(binary: &str, version: &str, network: &str, path: PathBuf) -> Self {
+        InstallOutcome {
+            binary: binary.to_string(),
+            version: version.to_string(),
+            network: network.to_string(),
+            newly_installed: false,
+            set_default: false,
+            path,
+            default_path: None,
+            alias: None,
+            target: None,
+        }
+    }
+
+    /// The version as it is spelled in a pinned spec: `testnet-v1.40.1` for a network-scoped
+    /// binary, `v0.0.8` for a standalone one
+    pub fn resolved_version(&self) -> String {
+        if NetworkOrBranch::from(self.network.as_str()) == NetworkOrBranch::Standalone {
+            self.version.clone()
+        } else {
+            format!("{}-{}", self.network, self.version)
+        }
+    }
+
+    /// The message printed for this outcome at the end of an install
+    pub fn summary(&self) -> String {
+        let InstallOutcome {
+            binary, version, ..
+        } = self;
+        match &self.target {
+            Some(target) if self.newly_installed => format!(
+                "Downloaded {binary}-{version} for {target} to {}\n",
+                display_path(&self.path)
+            ),
+            Some(target) => format!("Binary {binary}-{version} for {target} already downloaded\n"),
+            None if self.newly_installed || self.default_path.is_some() => {
+                let command = self.alias.as_deref().unwrap_or(binary);
+                let default = self
+                    .default_path
+                    .clone()
+                    .map(|default_path| (command, default_path));
+                install_summary(binary, version, &self.path, default)
+            }
+            None => format!("Binary {binary}-{version} already installed. Use `suiup default set` to change the default binary.\n"),
+        }
+    }
+}
+
+/// Records the installed binary and either offers to make it the default one, or, when an
+/// `alias` is given, installs it under that name in the default bin folder instead. With
+/// `no_default`, the binary is only recorded. The `companions` extracted with it are recorded
+/// on its entry and follow it into the default bin folder.
+#[allow(clippy::too_many_arguments)]
 pub fn install_binary(
     name: &str,
     network: String,
@@ -25,21 +111,168 @@ pub fn install_binary(
     debug: bool,
     binary_path: &Path,
     yes: bool,
-) -> Result<(), Error> {
-    let mut installed_binaries = InstalledBinaries::new()?;
-    installed_binaries.add_binary(BinaryVersion {
+    alias: Option<&str>,
+    companions: &[String],
+    no_default: bool,
+    released_at: Option<String>,
+) -> Result<InstallOutcome, Error> {
+    let binary = BinaryVersion {
         binary_name: name.to_string(),
         network_release: network.clone(),
         version: version.to_string(),
         debug,
         path: Some(binary_path.to_string_lossy().to_string()),
+        alias: alias.map(str::to_string),
+        installed_at: None,
+        target: None,
+        released_at,
+        companions: companions.to_vec(),
+    };
+    record_binary(binary.clone())?;
+    let set_default = if let Some(alias) = alias {
+        install_alias(binary_path, alias)?;
+        false
+    } else if no_default {
+        println!("The default version is left unchanged");
+        false
+    } else {
+        update_after_install(
+            &vec![name.to_string()],
+            network.clone(),
+            version,
+            debug,
+            yes,
+        )?
+    };
+    if set_default {
+        place_companions(&binary)?;
+    }
+    let default_path = (set_default || alias.is_some()).then(|| {
+        let path = get_default_bin_dir().join(alias.unwrap_or(name));
+        #[cfg(target_os = "windows")]
+        let path = path.with_extension("exe");
+        path
+    });
+    Ok(InstallOutcome {
+        newly_installed: true,
+        set_default,
+        default_path,
+        alias: alias.map(str::to_string),
+        ..InstallOutcome::present(name, version, &network, binary_path.to_path_buf())
+    })
+}
+
+/// Summary printed at the end of an install: where the versioned binary is, the copy in the
+/// default bin folder when one was made, and how to run it
+pub fn install_summary(
+    name: &str,
+    version: &str,
+    binary_path: &Path,
+    default: Option<(&str, PathBuf)>,
+) -> String {
+    let mut summary = format!(
+        "\nInstalled {name} {version} to {}\n",
+        display_path(binary_path)
+    );
+    match default {
+        Some((command, default_path)) => {
+            summary.push_str(&format!("Default: {}\n", display_path(&default_path)));
+            summary.push_str(&format!("Run it with: {command} --version\n"));
+        }
+        None => summary.push_str(&format!(
+            "Run it with: {} --version\n",
+            display_path(binary_path)
+        )),
+    }
+    summary
+}
+
+/// Adds the binary to the installed binaries file, stamped with the current time
+fn record_binary(binary: BinaryVersion) -> Result<(), Error> {
+    let mut installed_binaries = InstalledBinaries::new()?;
+    installed_binaries.add_binary(BinaryVersion {
+        installed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs()),
+        ..binary
     });
-    installed_binaries.save_to_file()?;
-    update_after_install(&vec![name.to_string()], network, version, debug, yes)?;
+    installed_binaries.save_to_file()
+}
+
+/// Parses an install manifest: one binary spec per line (e.g. `sui@testnet-1.40.1`), with blank
+/// lines and `#` comments ignored. Every spec is validated up front, so a typo is reported with
+/// its line number before anything is installed.
+pub fn parse_manifest(content: &str, config: &Config) -> Result<Vec<String>, Error> {
+    let mut specs = vec![];
+    for (idx, line) in content.lines().enumerate() {
+        let spec = line.split('#').next().unwrap_or_default().trim();
+        if spec.is_empty() {
+            continue;
+        }
+        parse_component_with_version(spec, config)
+            .map_err(|e| anyhow!("Invalid spec '{spec}' on line {}: {e}", idx + 1))?;
+        specs.push(spec.to_string());
+    }
+    if specs.is_empty() {
+        bail!("The manifest does not contain any binary to install");
+    }
+    Ok(specs)
+}
+
+/// Creates `dir` if needed and checks that files can be written to it, so an install fails before
+/// downloading anything rather than when copying the binary at the very end
+pub fn ensure_writable_dir(dir: &Path) -> Result<(), Error> {
+    std::fs::create_dir_all(dir)
+        .and_then(|_| tempfile::NamedTempFile::new_in(dir))
+        .map(drop)
+        .map_err(|e| anyhow!("Cannot write to {}: {e}", dir.display()))
+}
+
+/// Checks the names given with `--also`: companion binaries from the same release archive (e.g.
+/// `sui-tool`), which must be plain file names
+pub fn validate_also_binaries(main: &str, also: &[String]) -> Result<(), Error> {
+    for extra in also {
+        if extra.is_empty()
+            || extra.starts_with(['.', '-'])
+            || !extra
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        {
+            bail!("Invalid binary name for --also: '{extra}'");
+        }
+        if extra == main {
+            bail!("{extra} is already being installed, remove it from --also");
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a custom binary name is safe to use as a file name in the default bin folder and
+/// does not shadow one of the binaries managed by suiup.
+pub fn validate_binary_alias(alias: &str) -> Result<(), Error> {
+    if alias.is_empty() {
+        bail!("Binary name cannot be empty");
+    }
+    if alias.starts_with(['.', '-'])
+        || !alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!(
+            "Invalid binary name: {alias}. Use only letters, digits, '-', '_' and '.', and do not start with '.' or '-'"
+        );
+    }
+    if available_components().contains(&alias) {
+        bail!("Binary name {alias} is managed by suiup. Pick a different name.");
+    }
     Ok(())
 }
 
 // this is used for sui mostly
+/// Installs a binary, and the `also` companions, from a release archive. Returns an outcome per
+/// binary, the requested one first.
+#[allow(clippy::too_many_arguments)]
 pub async fn install_from_release(
     name: &str,
     network: &str,
@@ -48,42 +281,175 @@ pub async fn install_from_release(
     yes: bool,
     repo: Repo,
     github_token: Option<String>,
-) -> Result<(), Error> {
+    alias: Option<&str>,
+    checksum: Option<&Checksum>,
+    also: &[String],
+    target: Option<&str>,
+    no_default: bool,
+) -> Result<Vec<InstallOutcome>, Error> {
     let filename = match version_spec {
         Some(version) => {
-            download_release_at_version(repo, network, &version, github_token.clone()).await?
+            download_release_at_version(
+                repo,
+                network,
+                &version,
+                github_token.clone(),
+                checksum,
+                target,
+            )
+            .await?
+        }
+        None => {
+            download_latest_release(repo, network, github_token.clone(), checksum, target).await?
         }
-        None => download_latest_release(repo, network, github_token.clone()).await?,
     };
 
     let version = extract_version_from_release(&filename)?;
+    // the release list was fetched by the download above, so this does not call GitHub again
+    let released_at = release_list(&repo, github_token)
+        .await
+        .ok()
+        .and_then(|(releases, _)| release_published_at(&releases, &filename));
     let binary_name = if debug && name == "sui" {
         format!("{}-debug", name)
     } else {
         name.to_string()
     };
 
-    if !check_if_binaries_exist(&binary_name, network.to_string(), &version)? {
-        println!("Adding binary: {name}-{version}");
-        extract_component(&binary_name, network.to_string(), &filename)?;
+    let binary_path = release_binary_path(&binary_name, network, &version, target);
+    let is_installed = |binary: &str| match target {
+        Some(_) => Ok(release_binary_path(binary, network, &version, target).exists()),
+        None => check_if_binaries_exist(binary, network.to_string(), &version),
+    };
 
-        let binary_filename = format!("{}-{}", name, version);
-        #[cfg(target_os = "windows")]
-        let binary_filename = format!("{}.exe", binary_filename);
+    // companion binaries from the same archive, extracted in the same pass
+    let mut extras = vec![];
+    let mut present = vec![];
+    for extra in also {
+        if is_installed(extra)? {
+            let path = release_binary_path(extra, network, &version, target);
+            present.push(InstallOutcome {
+                target: target.map(str::to_string),
+                ..InstallOutcome::present(extra, &version, network, path)
+            });
+        } else {
+            extras.push(extra.as_str());
+        }
+    }
 
-        let binary_path = binaries_dir().join(network).join(binary_filename);
-        install_binary(
+    let main_missing = !is_installed(&binary_name)?;
+    let mut to_extract = extras.clone();
+    if main_missing {
+        to_extract.insert(0, binary_name.as_str());
+    }
+    if !to_extract.is_empty() {
+        extract_component(&to_extract, network.to_string(), &filename, target)?;
+    }
+
+    // the companions are recorded on the entry of the requested binary rather than on their own
+    let binary = BinaryVersion {
+        binary_name: name.to_string(),
+        network_release: network.to_string(),
+        version: version.clone(),
+        debug,
+        path: Some(binary_path.to_string_lossy().to_string()),
+        alias: None,
+        installed_at: None,
+        target: target.map(str::to_string),
+        released_at: released_at.clone(),
+        companions: also.to_vec(),
+    };
+    let mut outcomes: Vec<InstallOutcome> = extras
+        .iter()
+        .map(|extra| {
+            println!("Adding binary: {extra}-{version}");
+            let path = release_binary_path(extra, network, &version, target);
+            InstallOutcome {
+                newly_installed: true,
+                target: target.map(str::to_string),
+                ..InstallOutcome::present(extra, &version, network, path)
+            }
+        })
+        .collect();
+    outcomes.extend(present);
+
+    // binaries for another platform cannot run here, so they are only recorded
+    let main = if let Some(target) = target {
+        if main_missing {
+            record_binary(binary)?;
+        } else if !extras.is_empty() {
+            record_companions(binary)?;
+        }
+        InstallOutcome {
+            newly_installed: main_missing,
+            target: Some(target.to_string()),
+            ..InstallOutcome::present(name, &version, network, binary_path)
+        }
+    } else if main_missing || alias.is_some() {
+        if main_missing {
+            println!("Adding binary: {name}-{version}");
+        }
+        let main = install_binary(
             name,
             network.to_string(),
             &version,
             debug,
             &binary_path,
             yes,
+            alias,
+            also,
+            no_default,
+            released_at,
         )?;
+        InstallOutcome {
+            newly_installed: main_missing,
+            ..main
+        }
     } else {
-        println!("Binary {name}-{version} already installed. Use `suiup default set` to change the default binary.");
+        if !extras.is_empty() {
+            record_companions(binary.clone())?;
+            // the companions of the current default join it right away
+            if is_default(&binary)? {
+                place_companions(&binary)?;
+            }
+        }
+        InstallOutcome::present(name, &version, network, binary_path)
+    };
+
+    // the companions were made the default together with the requested binary
+    if main.set_default {
+        for outcome in &mut outcomes {
+            outcome.set_default = true;
+            let path = get_default_bin_dir().join(&outcome.binary);
+            #[cfg(target_os = "windows")]
+            let path = path.with_extension("exe");
+            outcome.default_path = Some(path);
+        }
     }
-    Ok(())
+    outcomes.insert(0, main);
+    Ok(outcomes)
+}
+
+/// Adds the companions of `binary` to its existing entry, leaving its install time as it is
+fn record_companions(binary: BinaryVersion) -> Result<(), Error> {
+    let mut installed_binaries = InstalledBinaries::new()?;
+    installed_binaries.add_binary(binary);
+    installed_binaries.save_to_file()
+}
+
+/// Whether `binary` is the default version of its binary. Debug defaults are keyed `sui` by
+/// install and `sui-debug` by `default set`, so both keys are checked.
+fn is_default(binary: &BinaryVersion) -> Result<bool, Error> {
+    let defaults = std::fs::read_to_string(default_file_path()?)?;
+    let defaults: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&defaults)?;
+    let name = &binary.binary_name;
+    Ok([name.clone(), format!("{name}-debug")].iter().any(|key| {
+        defaults.get(key).is_some_and(|(network, version, debug)| {
+            *network == binary.network_release
+                && *version == binary.version
+                && *debug == binary.debug
+        })
+    }))
 }
 
 /// Compile the code from the main branch or the specified branch.
@@ -93,7 +459,9 @@ pub async fn install_from_nightly(
     branch: &str,
     debug: bool,
     yes: bool,
-) -> Result<(), Error> {
+    alias: Option<&str>,
+    no_default: bool,
+) -> Result<InstallOutcome, Error> {
     println!("Installing {name} from {branch} branch");
     check_cargo_rust_installed()?;
 
@@ -140,22 +508,28 @@ pub async fn install_from_nightly(
     }
 
     println!("Installation completed successfully!");
-    // bin folder is needed because cargo installs in  /folder/bin/binary_name.
-    let orig_binary_path = binaries_folder_branch.join("bin").join(name.to_str());
+    // cargo config (e.g. `install.root`) can move the binary, so prefer the path cargo reports
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let orig_binary_path = match cargo_installed_path(&stderr, name.to_str()) {
+        Some(path) => path,
+        None => {
+            // bin folder is needed because cargo installs in  /folder/bin/binary_name.
+            let path = binaries_folder_branch.join("bin").join(name.to_str());
+            #[cfg(windows)]
+            let path = path.with_extension("exe");
+            path
+        }
+    };
+    debug!("Using the nightly build at {}", orig_binary_path.display());
 
     // rename the binary to `binary_name-nightly`, to keep things in sync across the board
 
-    let dst_name = if debug {
-        format!("{}-debug-nightly", name)
+    let dst_file = if debug {
+        format!("{}-debug", name)
     } else {
-        format!("{}-nightly", name)
+        name.to_string()
     };
-    let dst = binaries_folder_branch.join("bin").join(dst_name);
-
-    #[cfg(windows)]
-    let orig_binary_path = orig_binary_path.with_extension("exe");
-    #[cfg(windows)]
-    let dst = dst.with_extension("exe");
+    let dst = installed_binary_path(&dst_file, branch, "nightly");
 
     std::fs::rename(&orig_binary_path, &dst)?;
     install_binary(
@@ -165,8 +539,66 @@ pub async fn install_from_nightly(
         debug,
         &dst,
         yes,
-    )?;
+        alias,
+        &[],
+        no_default,
+        None,
+    )
+}
+
+/// Finds where `cargo install` put `binary`, from the `Installing <path>` (or `Replacing <path>`
+/// with `--force`) line of its stderr. The `Installing <crate> <version>` line printed before
+/// compiling is not a path to the binary and is skipped.
+pub fn cargo_installed_path(stderr: &str, binary: &str) -> Option<PathBuf> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("Installing ")
+                .or_else(|| line.strip_prefix("Replacing "))
+        })
+        .map(|path| PathBuf::from(path.trim()))
+        .filter(|path| path.parent().is_some_and(|dir| !dir.as_os_str().is_empty()))
+        .find(|path| path.file_stem().and_then(|stem| stem.to_str()) == Some(binary))
+}
 
+/// Resolves the release asset that installing the binary would download and prints its URL on
+/// the last line, without downloading anything
+pub async fn print_download_url(
+    name: &BinaryName,
+    network: &str,
+    version: Option<String>,
+    github_token: Option<String>,
+    target: Option<String>,
+) -> Result<(), Error> {
+    let target = target.map(|t| parse_target(&t)).transpose()?;
+    let url = match name.repo() {
+        Repo::Mvr => {
+            if target.is_some() {
+                bail!("--target is only available when installing from a release archive");
+            }
+            let mut installer = standalone::StandaloneInstaller::new(Repo::Mvr);
+            let (_, asset) = installer.find_asset(version).await?;
+            println!("Selected asset: {}", asset.name);
+            asset.browser_download_url.clone()
+        }
+        repo => {
+            // site-builder releases are only published for mainnet
+            let network = match repo.release_networks() {
+                [only] => only,
+                _ => network,
+            };
+            let target = target.as_deref();
+            let asset = match version {
+                Some(version) => {
+                    find_asset_at_version(repo, network, &version, github_token, target).await?
+                }
+                None => find_latest_asset(repo, network, github_token, target).await?,
+            };
+            asset.browser_download_url
+        }
+    };
+    println!("{url}");
     Ok(())
 }
 
@@ -174,45 +606,56 @@ pub async fn install_standalone(
     version: Option<String>,
     repo: Repo,
     yes: bool,
-) -> Result<(), Error> {
+    alias: Option<&str>,
+    checksum: Option<&Checksum>,
+    no_default: bool,
+) -> Result<InstallOutcome, Error> {
     let network = "standalone".to_string();
     let binary_name = repo.binary_name();
     if !check_if_binaries_exist(
-        &binary_name,
+        binary_name,
         network.clone(),
         &version.clone().unwrap_or_default(),
     )? {
         let mut installer = standalone::StandaloneInstaller::new(repo);
-        let installed_version = installer.download_version(version).await?;
+        let installed_version = installer.download_version(version, checksum).await?;
+        let released_at = installer.published_at(&installed_version);
 
         println!("Adding binary: {binary_name}-{installed_version}");
 
-        let binary_path = binaries_dir()
-            .join(&network)
-            .join(format!("{}-{}", binary_name, installed_version));
+        let binary_path = installed_binary_path(binary_name, &network, &installed_version);
         #[cfg(feature = "nix-patchelf")]
         {
             if let Err(e) = crate::patchelf::patch_binary(&binary_path) {
-                println!("Warning: Failed to patch binary with patchelf: {}", e);
-                println!(
+                eprintln!("Warning: Failed to patch binary with patchelf: {}", e);
+                eprintln!(
                     "The binary may not work correctly. Ensure nix-runtime-deps.json is installed."
                 );
             }
         }
         install_binary(
-            &binary_name,
+            binary_name,
             network,
             &installed_version,
             false,
             &binary_path,
             yes,
-        )?;
+            alias,
+            &[],
+            no_default,
+            released_at,
+        )
     } else {
-        let version = version.unwrap_or_default();
-        println!("Binary {binary_name}-{version} already installed. Use `suiup default set {binary_name} {version}` to set the default version to the specified one.");
+        // only reached with a version, found with its `v` prefix
+        let version = ensure_version_prefix(&version.unwrap_or_default());
+        let path = installed_binary_path(binary_name, &network, &version);
+        Ok(InstallOutcome::present(
+            binary_name,
+            &version,
+            &network,
+            path,
+        ))
     }
-
-    Ok(())
 }
 
 fn check_cargo_rust_installed() -> Result<(), Error> {
@@ -244,3 +687,17 @@ fn check_cargo_rust_installed() -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_version() {
+        let path = PathBuf::from("sui-v1.40.1");
+        let outcome = InstallOutcome::present("sui", "v1.40.1", "testnet", path.clone());
+        assert_eq!(outcome.resolved_version(), "testnet-v1.40.1");
+        let outcome = InstallOutcome::present("mvr", "v0.0.8", "standalone", path);
+        assert_eq!(outcome.resolved_version(), "v0.0.8");
+    }
+}
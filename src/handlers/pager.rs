@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use console::Term;
+
+/// Prints `output`, through a pager when stdout is a terminal and `output` does not fit on the
+/// screen, like git does. The pager is `$PAGER`, then `less -R`, and the output is printed
+/// directly when neither can be started.
+pub fn page_output(output: &str, no_pager: bool) -> Result<()> {
+    let term = Term::stdout();
+    if no_pager || !term.is_term() || !needs_paging(output, term.size().0) {
+        print!("{output}");
+        return Ok(());
+    }
+
+    for pager in pager_commands(std::env::var("PAGER").ok()) {
+        let Some((program, args)) = pager.split_first() else {
+            continue;
+        };
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            // the pager may be quit before reading everything, which is fine
+            let _ = stdin.write_all(output.as_bytes());
+        }
+        child.wait()?;
+        return Ok(());
+    }
+
+    print!("{output}");
+    Ok(())
+}
+
+/// Whether `output` has more lines than fit on a terminal `height` rows high
+pub fn needs_paging(output: &str, height: u16) -> bool {
+    output.lines().count() >= height as usize
+}
+
+/// Pager commands to try in order: `$PAGER` when it is set, then `less -R`. An empty `$PAGER`
+/// (or `cat`) disables paging, as with git.
+pub fn pager_commands(pager: Option<String>) -> Vec<Vec<String>> {
+    match pager {
+        Some(pager) if pager.trim().is_empty() || pager.trim() == "cat" => vec![],
+        Some(pager) => vec![
+            pager.split_whitespace().map(str::to_string).collect(),
+            vec!["less".to_string(), "-R".to_string()],
+        ],
+        None => vec![vec!["less".to_string(), "-R".to_string()]],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_paging() {
+        assert!(!needs_paging("a\nb\n", 24));
+        assert!(needs_paging(&"line\n".repeat(24), 24));
+    }
+
+    #[test]
+    fn test_pager_commands() {
+        let less = vec!["less".to_string(), "-R".to_string()];
+        assert_eq!(pager_commands(None), vec![less.clone()]);
+        assert_eq!(
+            pager_commands(Some("most -s".to_string())),
+            vec![vec!["most".to_string(), "-s".to_string()], less]
+        );
+        assert!(pager_commands(Some(String::new())).is_empty());
+        assert!(pager_commands(Some("cat".to_string())).is_empty());
+    }
+}
@@ -4,38 +4,262 @@
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Error;
+use futures_util::future::join_all;
+use lazy_static::lazy_static;
+use reqwest::header::HeaderMap;
 use reqwest::header::ETAG;
 use reqwest::header::IF_NONE_MATCH;
-
-use crate::handlers::version::extract_version_from_release;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::date::format_date;
+use crate::handlers::download::{retry_with_backoff, RETRY_ATTEMPTS, RETRY_DELAY};
+use crate::handlers::version::{compare_versions, extract_version_from_release};
 use crate::paths::get_suiup_cache_dir;
 use crate::types::Release;
 use crate::types::Repo;
 
-/// Fetches the list of releases from the GitHub repository
+type ReleaseList = (Vec<Release>, Option<String>);
+
+/// The GitHub API request budget, from the `X-RateLimit-*` headers of a response
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    pub remaining: u64,
+    pub limit: u64,
+}
+
+impl RateLimit {
+    /// Reads the budget from response headers, if GitHub sent them
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.parse().ok();
+        Some(Self {
+            remaining: header("x-ratelimit-remaining")?,
+            limit: header("x-ratelimit-limit")?,
+        })
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} requests remaining", self.remaining, self.limit)
+    }
+}
+
+/// What GitHub makes of the configured token, see [`check_github_token`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenStatus {
+    /// No token is configured, so requests get the anonymous budget of 60 an hour
+    Missing(Option<RateLimit>),
+    /// GitHub accepted the token
+    Valid(Option<RateLimit>),
+    /// GitHub rejected the token, e.g. because it expired or was revoked
+    Invalid,
+}
+
+impl fmt::Display for TokenStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenStatus::Missing(Some(rate_limit)) => write!(
+                f,
+                "not configured ({rate_limit}), set GITHUB_TOKEN for a higher limit"
+            ),
+            TokenStatus::Missing(None) => write!(
+                f,
+                "not configured (60 requests an hour), set GITHUB_TOKEN for a higher limit"
+            ),
+            TokenStatus::Valid(Some(rate_limit)) => write!(f, "valid ({rate_limit})"),
+            TokenStatus::Valid(None) => write!(f, "valid"),
+            TokenStatus::Invalid => write!(f, "rejected by GitHub, it may be expired or revoked"),
+        }
+    }
+}
+
+/// Asks GitHub for the rate limit of `token`, which tells whether the token is accepted without
+/// using up any of the request budget
+pub async fn check_github_token(token: Option<&str>) -> Result<TokenStatus, Error> {
+    let mut request = reqwest::Client::new()
+        .get("https://api.github.com/rate_limit")
+        .header("User-Agent", "suiup");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("token {token}"));
+    }
+    let response = request.send().await?;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        return Ok(TokenStatus::Invalid);
+    }
+    let response = response.error_for_status()?;
+    let rate_limit = RateLimit::from_headers(response.headers());
+    debug!("GitHub rate limit for the token: {rate_limit:?}");
+    Ok(match token {
+        Some(_) => TokenStatus::Valid(rate_limit),
+        None => TokenStatus::Missing(rate_limit),
+    })
+}
+
+lazy_static! {
+    /// Release lists already fetched by this process, keyed by repository
+    static ref RELEASE_CACHE: Mutex<HashMap<String, ReleaseList>> = Mutex::new(HashMap::new());
+    /// Background refreshes of cached release lists started with `--offline-first`
+    static ref REFRESHES: Mutex<Vec<JoinHandle<()>>> = Mutex::new(vec![]);
+}
+
+static OFFLINE_FIRST: OnceLock<bool> = OnceLock::new();
+
+/// Serves release lists from the cache, whatever their age, and refreshes them in the background
+/// for the next invocation. Only the first call has an effect.
+pub fn set_offline_first(offline_first: bool) {
+    let _ = OFFLINE_FIRST.set(offline_first);
+}
+
+fn offline_first() -> bool {
+    OFFLINE_FIRST.get().copied().unwrap_or(false)
+}
+
+/// Fetches the list of releases from the GitHub repository. The list is fetched at most once per
+/// repository and process, later calls reuse it.
+///
+/// With `--offline-first`, a list cached by an earlier invocation is returned right away, so it
+/// may be slightly stale, and a fresh one is fetched in the background.
 pub async fn release_list(
     repo: &Repo,
     github_token: Option<String>,
-) -> Result<(Vec<Release>, Option<String>), anyhow::Error> {
-    let release_url = format!("https://api.github.com/repos/{}/releases", repo);
-    let client = reqwest::Client::new();
-    let mut request = client.get(&release_url).header("User-Agent", "suiup");
+) -> Result<ReleaseList, anyhow::Error> {
+    cached_release_list(repo, || async {
+        if offline_first() {
+            if let Some((releases, saved_at)) = load_stale_release_list(repo) {
+                debug!("Using the release list of {repo} cached on {saved_at}, refreshing it");
+                let etag = read_etag_file(repo).ok();
+                let repo = *repo;
+                let refresh = tokio::spawn(async move {
+                    if let Err(e) = refresh_release_list(repo, github_token).await {
+                        debug!("Could not refresh the release list of {repo}: {e}");
+                    }
+                });
+                REFRESHES.lock().unwrap().push(refresh);
+                return Ok((releases, etag));
+            }
+            debug!("No release list of {repo} cached yet, fetching it");
+        }
+        fetch_release_list(repo, github_token).await
+    })
+    .await
+}
 
-    // Add authorization header if token is provided
-    if let Some(token) = github_token {
-        request = request.header("Authorization", format!("token {}", token));
+/// Waits up to `timeout` for the background refreshes of `--offline-first` to save their release
+/// lists. Refreshes still running afterwards are cut short when the process exits, which leaves
+/// the cached list as it was since it is replaced atomically.
+pub async fn wait_for_refreshes(timeout: Duration) {
+    let refreshes = std::mem::take(&mut *REFRESHES.lock().unwrap());
+    if !refreshes.is_empty() {
+        let _ = tokio::time::timeout(timeout, join_all(refreshes)).await;
     }
+}
 
-    // Add ETag for caching
-    if let Ok(etag) = read_etag_file(repo) {
-        request = request.header(IF_NONE_MATCH, etag);
+/// Returns the release list of `repo` from the in-process cache, calling `fetch` only when it is
+/// not cached yet
+async fn cached_release_list<F, Fut>(repo: &Repo, fetch: F) -> Result<ReleaseList, anyhow::Error>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<ReleaseList, anyhow::Error>>,
+{
+    let key = repo.to_string();
+    if let Some(releases) = RELEASE_CACHE.lock().unwrap().get(&key) {
+        return Ok(releases.clone());
     }
+    let releases = fetch().await?;
+    RELEASE_CACHE.lock().unwrap().insert(key, releases.clone());
+    Ok(releases)
+}
+
+async fn fetch_release_list(
+    repo: &Repo,
+    github_token: Option<String>,
+) -> Result<ReleaseList, anyhow::Error> {
+    let response = match request_release_list(repo, github_token).await {
+        Ok(response) => response,
+        Err(e) => match load_stale_release_list(repo) {
+            Some((releases, saved_at)) => {
+                eprintln!("GitHub unreachable, using cached release list from {saved_at}");
+                return Ok((releases, None));
+            }
+            None => return Err(no_cached_data(repo, e)),
+        },
+    };
+    save_release_list_response(repo, response, false).await
+}
 
-    let response = request
-        .send()
-        .await
-        .map_err(|e| anyhow!("Could not send request: {e}"))?;
+/// The error for a release list that can neither be fetched nor loaded from the cache, which
+/// tells how to fill the cache instead of only reporting the network error
+fn no_cached_data(repo: &Repo, e: Error) -> Error {
+    anyhow!(
+        "No cached data for {repo} and GitHub cannot be reached ({e}). Run 'suiup cache warm {}' once while online to populate the cache.",
+        repo.binary_name()
+    )
+}
+
+/// Fetches the release list of `repo` again and saves it to the cache, without printing anything,
+/// so the next invocation uses a fresh list
+async fn refresh_release_list(repo: Repo, github_token: Option<String>) -> Result<(), Error> {
+    let response = request_release_list(&repo, github_token).await?;
+    save_release_list_response(&repo, response, true).await?;
+    debug!("Refreshed the cached release list of {repo}");
+    Ok(())
+}
+
+/// Requests the release list of `repo` from GitHub, retrying on network and server errors
+async fn request_release_list(
+    repo: &Repo,
+    github_token: Option<String>,
+) -> Result<reqwest::Response, anyhow::Error> {
+    let release_url = format!("https://api.github.com/repos/{}/releases", repo);
+    let client = reqwest::Client::new();
+    let etag = read_etag_file(repo).ok();
+
+    let response = retry_with_backoff(RETRY_ATTEMPTS, RETRY_DELAY, || async {
+        let mut request = client.get(&release_url).header("User-Agent", "suiup");
+
+        // Add authorization header if token is provided
+        if let Some(token) = &github_token {
+            request = request.header("Authorization", format!("token {}", token));
+        }
 
+        // Add ETag for caching
+        if let Some(etag) = &etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow!("Could not send request: {e}"))?;
+        if response.status().is_server_error() {
+            bail!("GitHub returned {}", response.status());
+        }
+        Ok(response)
+    })
+    .await?;
+
+    if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+        debug!("GitHub API: {rate_limit}");
+    }
+    Ok(response)
+}
+
+/// Reads the release list from a GitHub response and saves it to the cache. A `304 Not Modified`
+/// response returns the cached list. `quiet` is set for background refreshes, which must not
+/// print in the middle of the command output.
+async fn save_release_list_response(
+    repo: &Repo,
+    response: reqwest::Response,
+    quiet: bool,
+) -> Result<ReleaseList, anyhow::Error> {
     // note this only works with authenticated requests. Should add support for that later.
     if response.status() == reqwest::StatusCode::NOT_MODIFIED {
         // If nothing has changed, return an empty list and the existing ETag
@@ -53,6 +277,9 @@ pub async fn release_list(
         .map(String::from);
     let response = response.error_for_status()?;
     let releases: Vec<Release> = response.json().await?;
+    if !quiet {
+        println!("Saving releases list to cache");
+    }
     save_release_list(repo, &releases, etag.clone())?;
 
     Ok((releases, etag))
@@ -71,14 +298,64 @@ fn read_etag_file(repo: &Repo) -> Result<String, anyhow::Error> {
     }
 }
 
-/// Finds the last release for a given network
+/// Finds the last release for a given network, with only the assets of that network
 pub async fn find_last_release_by_network(
     releases: Vec<Release>,
     network: &str,
 ) -> Option<Release> {
+    latest_release(&releases, network).map(|release| release_for_network(release, network))
+}
+
+/// The release with only its assets for `network` (or another part of their name, such as
+/// `testnet-v1.40.1`). Some tags bundle the assets of several networks, whose versions differ.
+/// A release without any such asset is returned as it is.
+pub fn release_for_network(release: &Release, network: &str) -> Release {
+    let assets: Vec<_> = release
+        .assets
+        .iter()
+        .filter(|a| a.name.contains(network))
+        .cloned()
+        .collect();
+    if assets.is_empty() {
+        return release.clone();
+    }
+    Release {
+        assets,
+        ..release.clone()
+    }
+}
+
+/// The version of the assets of `release` for `network`
+fn network_version(release: &Release, network: &str) -> Option<String> {
+    release
+        .assets
+        .iter()
+        .filter(|a| a.name.contains(network))
+        .find_map(|a| extract_version_from_release(&a.name).ok())
+}
+
+/// Picks the release with the highest version for `network`, using the publish date to break ties
+/// between releases that share a version. Releases tied on both keep the GitHub order, newest
+/// first.
+fn latest_release<'a>(releases: &'a [Release], network: &str) -> Option<&'a Release> {
+    let with_versions = releases
+        .iter()
+        .filter_map(|release| Some((release, network_version(release, network)?)));
+    // max_by returns the last of equal elements, so walk the list backwards
+    with_versions
+        .rev()
+        .max_by(|(a, a_version), (b, b_version)| {
+            compare_versions(a_version, b_version).then_with(|| a.published_at.cmp(&b.published_at))
+        })
+        .map(|(release, _)| release)
+}
+
+/// When the release that contains the asset `asset_name` was published
+pub fn release_published_at(releases: &[Release], asset_name: &str) -> Option<String> {
     releases
-        .into_iter()
-        .find(|r| r.assets.iter().any(|a| a.name.contains(network)))
+        .iter()
+        .find(|r| r.assets.iter().any(|a| a.name == asset_name))
+        .and_then(|r| r.published_at.clone())
 }
 
 fn save_release_list(
@@ -86,7 +363,7 @@ fn save_release_list(
     releases: &[Release],
     etag: Option<String>,
 ) -> Result<(), anyhow::Error> {
-    println!("Saving releases list to cache");
+    debug!("Saving the release list of {repo} to cache");
     let repo_name = repo.to_string();
     let repo_name = repo_name.replace("/", "_");
     let etag_filename = format!("etag_{}.txt", repo_name);
@@ -100,19 +377,31 @@ fn save_release_list(
     let cache_content =
         serde_json::to_string_pretty(releases).expect("Could not serialize releases file: {}");
 
-    std::fs::write(&cache_file, cache_content).map_err(|_| {
+    write_replacing(&cache_file, cache_content).map_err(|_| {
         anyhow!(
             "Could not write cache releases file: {}",
             cache_file.display(),
         )
     })?;
     if let Some(etag) = etag {
-        std::fs::write(&etag_file, etag)
+        write_replacing(&etag_file, etag)
             .map_err(|_| anyhow!("Could not write ETag file: {}", etag_file.display()))?;
     }
     Ok(())
 }
 
+/// Writes `contents` to a temporary file next to `path`, then renames it over `path`, so readers
+/// never see a partly written file, even when a background refresh is cut short
+fn write_replacing(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+    std::fs::write(&tmp, contents)
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&tmp);
+        })
+}
+
 fn load_cached_release_list(repo: &Repo) -> Result<Option<(Vec<Release>, String)>, anyhow::Error> {
     let repo_name = repo.to_string();
     let repo_name = repo_name.replace("/", "_");
@@ -141,20 +430,29 @@ fn load_cached_release_list(repo: &Repo) -> Result<Option<(Vec<Release>, String)
     }
 }
 
+/// Loads the release list saved by an earlier run regardless of its ETag, together with the date
+/// it was saved, for when GitHub cannot be reached
+fn load_stale_release_list(repo: &Repo) -> Option<(Vec<Release>, String)> {
+    let repo_name = repo.to_string().replace("/", "_");
+    let cache_file = get_suiup_cache_dir().join(format!("releases_{}.txt", repo_name));
+    let releases = serde_json::from_str(&std::fs::read_to_string(&cache_file).ok()?).ok()?;
+    let saved_at = cache_file
+        .metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| format_date(d.as_secs()))
+        .unwrap_or_else(|| "an unknown date".to_string());
+    Some((releases, saved_at))
+}
+
 pub async fn last_release_for_network<'a>(
     releases: &'a [Release],
     network: &'a str,
 ) -> Result<(&'a str, String), Error> {
-    if let Some(release) = releases
-        .iter()
-        .find(|r| r.assets.iter().any(|a| a.name.contains(network)))
-    {
-        Ok((
-            network,
-            extract_version_from_release(release.assets[0].name.as_str())?,
-        ))
-    } else {
-        bail!("No release found for {network}")
+    match latest_release(releases, network).and_then(|r| network_version(r, network)) {
+        Some(version) => Ok((network, version)),
+        None => bail!("No release found for {network}"),
     }
 }
 
@@ -202,6 +500,7 @@ mod tests {
                     browser_download_url: format!("https://example.com/{}", name),
                 })
                 .collect(),
+            published_at: None,
         }
     }
 
@@ -236,6 +535,135 @@ mod tests {
         assert!(networks.contains(&"testnet".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_latest_release_breaks_ties_by_publish_date() {
+        let release = |name: &str, published_at: &str| Release {
+            published_at: Some(published_at.to_string()),
+            ..create_test_release(vec![name])
+        };
+        let releases = vec![
+            release(
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+                "2025-01-10T00:00:00Z",
+            ),
+            release(
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+                "2025-01-20T00:00:00Z",
+            ),
+            release(
+                "sui-testnet-v1.39.3-ubuntu-x86_64.tgz",
+                "2025-01-30T00:00:00Z",
+            ),
+            release(
+                "sui-devnet-v1.41.0-ubuntu-x86_64.tgz",
+                "2025-01-25T00:00:00Z",
+            ),
+        ];
+
+        let latest = find_last_release_by_network(releases.clone(), "testnet")
+            .await
+            .unwrap();
+        assert_eq!(latest.published_at.as_deref(), Some("2025-01-20T00:00:00Z"));
+        assert_eq!(
+            last_release_for_network(&releases, "testnet")
+                .await
+                .unwrap(),
+            ("testnet", "v1.40.1".to_string())
+        );
+
+        // without publish dates, the first listed release of a version wins
+        let releases: Vec<Release> = releases
+            .into_iter()
+            .map(|r| Release {
+                published_at: None,
+                ..r
+            })
+            .collect();
+        let latest = latest_release(&releases[..3], "testnet").unwrap();
+        assert!(std::ptr::eq(latest, &releases[0]));
+    }
+
+    #[tokio::test]
+    async fn test_mixed_network_release() {
+        // one tag with the assets of two networks, mainnet listed first
+        let releases = vec![
+            create_test_release(vec![
+                "sui-mainnet-v1.39.3-ubuntu-x86_64.tgz",
+                "sui-testnet-v1.40.1-macos-arm64.tgz",
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+            ]),
+            create_test_release(vec!["sui-testnet-v1.40.0-ubuntu-x86_64.tgz"]),
+        ];
+        assert_eq!(
+            last_release_for_network(&releases, "testnet")
+                .await
+                .unwrap(),
+            ("testnet", "v1.40.1".to_string())
+        );
+        assert_eq!(
+            last_release_for_network(&releases, "mainnet")
+                .await
+                .unwrap(),
+            ("mainnet", "v1.39.3".to_string())
+        );
+
+        let latest = find_last_release_by_network(releases, "testnet")
+            .await
+            .unwrap();
+        let names: Vec<_> = latest.assets.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "sui-testnet-v1.40.1-macos-arm64.tgz",
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_published_at() {
+        let releases = vec![Release {
+            published_at: Some("2025-01-20T00:00:00Z".to_string()),
+            ..create_test_release(vec!["sui-testnet-v1.40.1-ubuntu-x86_64.tgz"])
+        }];
+        assert_eq!(
+            release_published_at(&releases, "sui-testnet-v1.40.1-ubuntu-x86_64.tgz").as_deref(),
+            Some("2025-01-20T00:00:00Z")
+        );
+        assert_eq!(
+            release_published_at(&releases, "sui-testnet-v1.40.1-macos-arm64.tgz"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimit::from_headers(&headers), None);
+
+        headers.insert("X-RateLimit-Remaining", "47".parse().unwrap());
+        headers.insert("X-RateLimit-Limit", "60".parse().unwrap());
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(rate_limit.to_string(), "47/60 requests remaining");
+    }
+
+    #[test]
+    fn test_token_status_display() {
+        let rate_limit = RateLimit {
+            remaining: 4990,
+            limit: 5000,
+        };
+        assert_eq!(
+            TokenStatus::Valid(Some(rate_limit)).to_string(),
+            "valid (4990/5000 requests remaining)"
+        );
+        assert_eq!(
+            TokenStatus::Missing(None).to_string(),
+            "not configured (60 requests an hour), set GITHUB_TOKEN for a higher limit"
+        );
+        assert!(TokenStatus::Invalid.to_string().contains("rejected"));
+    }
+
     #[test]
     fn test_ensure_version_prefix() {
         assert_eq!(ensure_version_prefix("1.53.0"), "v1.53.0");
@@ -243,4 +671,36 @@ mod tests {
         assert_eq!(ensure_version_prefix("0.1.2"), "v0.1.2");
         assert_eq!(ensure_version_prefix("v2.0.0"), "v2.0.0");
     }
+
+    #[tokio::test]
+    async fn test_release_list_fetched_once_per_repo() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            Ok((
+                vec![create_test_release(vec![
+                    "site-builder-testnet-v1.0.1-ubuntu-x86_64.tgz",
+                ])],
+                None,
+            ))
+        };
+
+        let (releases, _) = cached_release_list(&Repo::WalrusSites, fetch)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+        let (releases, _) = cached_release_list(&Repo::WalrusSites, fetch)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // a failed fetch is not cached
+        let result = cached_release_list(&Repo::Walrus, || async { bail!("offline") }).await;
+        assert!(result.is_err());
+        cached_release_list(&Repo::Walrus, fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
 }
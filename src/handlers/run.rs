@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+use anyhow::{anyhow, bail, Result};
+use tokio::process::{Child, Command};
+
+use crate::handlers::binary_source_path;
+use crate::handlers::switch::{find_matching_binary, parse_binary_spec};
+use crate::paths::get_default_bin_dir;
+use crate::types::InstalledBinaries;
+
+/// Handles the `run` command. The binary runs as a child of suiup, which forwards the signals it
+/// receives and exits with the child's exit code, so it behaves like a transparent shim.
+pub async fn handle_run(binary_spec: &str, args: &[String]) -> Result<()> {
+    let path = resolve_binary(binary_spec)?;
+    let mut child = Command::new(&path)
+        .args(args)
+        .spawn()
+        .map_err(|e| anyhow!("Cannot run {}: {e}", path.display()))?;
+
+    let status = wait_forwarding_signals(&mut child).await?;
+    match exit_code(status) {
+        0 => Ok(()),
+        code => std::process::exit(code),
+    }
+}
+
+/// Resolves `binary` to the default binary, `binary@network_release` to the latest installed
+/// version for that network/release and `binary@network_release-version` to that exact version.
+fn resolve_binary(binary_spec: &str) -> Result<PathBuf> {
+    if binary_spec.contains('@') {
+        let (binary_name, network_release, version) = parse_binary_spec(binary_spec)?;
+        let installed_binaries = InstalledBinaries::new()?;
+        let binary = find_matching_binary(
+            &installed_binaries,
+            &binary_name,
+            &network_release,
+            version.as_deref(),
+        )?;
+        let path = binary_source_path(&binary);
+        if !path.is_file() {
+            bail!(
+                "Binary {binary} from {network_release} is not found at {}. Reinstall it with `suiup install`.",
+                path.display()
+            );
+        }
+        return Ok(path);
+    }
+
+    let path = get_default_bin_dir().join(binary_spec);
+    #[cfg(target_os = "windows")]
+    let path = path.with_extension("exe");
+    if !path.is_file() {
+        bail!(
+            "No default {binary_spec} found in {}. Install it with `suiup install {binary_spec}` or pass `{binary_spec}@<network>`.",
+            get_default_bin_dir().display()
+        );
+    }
+    Ok(path)
+}
+
+#[cfg(unix)]
+async fn wait_forwarding_signals(child: &mut Child) -> Result<ExitStatus> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let pid = child
+        .id()
+        .ok_or_else(|| anyhow!("Cannot get the process id of the child"))?
+        as libc::pid_t;
+    let mut sigint = signal(SignalKind::interrupt())?;
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sighup = signal(SignalKind::hangup())?;
+
+    let forward = |sig: libc::c_int| {
+        // SAFETY: kill has no memory safety requirements, at worst the child has already exited
+        unsafe { libc::kill(pid, sig) };
+    };
+
+    loop {
+        tokio::select! {
+            status = child.wait() => return Ok(status?),
+            _ = sigint.recv() => forward(libc::SIGINT),
+            _ = sigterm.recv() => forward(libc::SIGTERM),
+            _ = sighup.recv() => forward(libc::SIGHUP),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_forwarding_signals(child: &mut Child) -> Result<ExitStatus> {
+    // Ctrl-C reaches every process attached to the console, so suiup only has to stay alive
+    // until the child exits
+    loop {
+        tokio::select! {
+            status = child.wait() => return Ok(status?),
+            _ = tokio::signal::ctrl_c() => {}
+        }
+    }
+}
+
+/// Maps the child's exit status to suiup's exit code, using the shell convention of 128 + signal
+/// number when the child was killed by a signal.
+fn exit_code(status: ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    1
+}
@@ -4,25 +4,31 @@
 use super::download::detect_os_arch;
 
 use crate::handlers::download::download_file;
-use anyhow::{anyhow, Result};
-use std::{fmt::Display, process::Command};
+use crate::handlers::extract_archive_entry;
+use crate::handlers::release::{check_github_token, TokenStatus};
+use crate::handlers::version::SemVer;
+use crate::paths::get_suiup_data_dir;
+use crate::symbols::symbols;
+use anyhow::{anyhow, bail, Result};
+use std::fmt;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use tokio::task;
 
-use flate2::read::GzDecoder;
-use serde::Deserialize;
-use std::fs::File;
-use tar::Archive;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
 }
 
-pub fn check_for_updates() {
-    task::spawn(check_for_updates_impl());
+pub fn check_for_updates(github_token: Option<String>) {
+    task::spawn(check_for_updates_impl(github_token));
 }
 
-async fn check_for_updates_impl() -> Option<()> {
+async fn check_for_updates_impl(github_token: Option<String>) -> Option<()> {
     let current_exe = std::env::current_exe().ok()?;
     let output = std::process::Command::new(current_exe)
         .arg("--version")
@@ -31,25 +37,37 @@ async fn check_for_updates_impl() -> Option<()> {
 
     let version_output = String::from_utf8(output.stdout).ok()?;
     let version = version_output.split_whitespace().nth(1)?;
-    let current_version = Ver::from_str(version).ok()?;
+    let current_version = version.parse::<SemVer>().ok()?;
 
-    let latest_version = get_latest_version().await.ok()?;
+    let latest_version = get_latest_version(github_token).await.ok()?;
 
     if current_version < latest_version {
+        let symbols = symbols();
         eprintln!(
-            "\n⚠️  A new version of suiup is available: v{} → v{}",
-            current_version, latest_version
+            "\n{} A new version of suiup is available: v{} {} v{}",
+            symbols.notice, current_version, symbols.arrow, latest_version
         );
         eprintln!("   Run 'suiup self update' to update to the latest version.\n");
     }
     Some(())
 }
 
-async fn get_latest_version() -> Result<Ver> {
+/// Builds the request for the latest suiup release on GitHub
+fn latest_release_request(github_token: Option<&str>) -> reqwest::RequestBuilder {
     let client = reqwest::Client::new();
-    let response = client
+    let mut request = client
         .get("https://api.github.com/repos/MystenLabs/suiup/releases/latest")
-        .header("User-Agent", "suiup")
+        .header("User-Agent", "suiup");
+
+    // Add authorization header if token is provided
+    if let Some(token) = github_token {
+        request = request.header("Authorization", format!("token {}", token));
+    }
+    request
+}
+
+async fn get_latest_version(github_token: Option<String>) -> Result<SemVer> {
+    let response = latest_release_request(github_token.as_deref())
         .send()
         .await?;
 
@@ -58,47 +76,66 @@ async fn get_latest_version() -> Result<Ver> {
     }
 
     let release: GitHubRelease = response.json().await?;
-    Ver::from_str(&release.tag_name)
+    release.tag_name.parse()
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct Ver {
-    major: usize,
-    minor: usize,
-    patch: usize,
+/// Exit code of `self update --check` when a newer suiup is released
+pub const UPDATE_AVAILABLE_EXIT_CODE: i32 = 100;
+
+/// Returned by `self update --check` when a newer suiup is released. The message was already
+/// printed, suiup only exits with [`UPDATE_AVAILABLE_EXIT_CODE`].
+#[derive(Debug)]
+pub struct UpdateAvailable;
+
+impl UpdateAvailable {
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        UPDATE_AVAILABLE_EXIT_CODE
+    }
 }
 
-impl Ver {
-    fn from_str(s: &str) -> Result<Self> {
-        let parts: Vec<&str> = s.split('.').collect();
-        if parts.len() != 3 {
-            return Err(anyhow::anyhow!("Invalid version format"));
-        }
-        let major = if parts[0].starts_with('v') {
-            parts[0][1..].parse::<usize>()?
-        } else {
-            parts[0].parse::<usize>()?
-        };
-
-        let minor = parts[1].parse::<usize>()?;
-        let patch = parts[2].parse::<usize>()?;
-        Ok(Ver {
-            major,
-            minor,
-            patch,
-        })
+impl fmt::Display for UpdateAvailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A newer suiup is available")
+    }
+}
+
+impl std::error::Error for UpdateAvailable {}
+
+/// Handles `self update --check`: compares the running version with the latest release, and fails
+/// with [`UpdateAvailable`] when an update is available
+pub async fn handle_check_update(github_token: Option<String>) -> Result<()> {
+    let current_version = BUILD_INFO.version.parse::<SemVer>()?;
+    let latest_version = get_latest_version(github_token).await?;
+    let (message, update_available) = update_status(&current_version, &latest_version);
+    println!("{message}");
+    if update_available {
+        return Err(UpdateAvailable.into());
     }
+    Ok(())
 }
 
-impl Display for Ver {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+/// The message of `self update --check`, and whether `latest` is newer than `current`
+fn update_status(current: &SemVer, latest: &SemVer) -> (String, bool) {
+    if current < latest {
+        let arrow = symbols().arrow;
+        (
+            format!("Update available: suiup v{current} {arrow} v{latest}. Run 'suiup self update' to install it."),
+            true,
+        )
+    } else {
+        (
+            format!("suiup v{current} is up to date (latest release: v{latest})"),
+            false,
+        )
     }
 }
 
-pub async fn handle_update() -> Result<()> {
+/// Updates suiup to the latest release. With `dry_run`, only prints the update that would be
+/// applied without downloading or replacing anything.
+pub async fn handle_update(github_token: Option<String>, dry_run: bool) -> Result<()> {
     // find the current binary version
-    let current_exe = std::env::current_exe()?;
+    let current_exe = replaceable_exe()?;
     let current_version = Command::new(&current_exe).arg("--version").output()?.stdout;
     let current_version = String::from_utf8(current_version)?.trim().to_string();
 
@@ -116,14 +153,10 @@ pub async fn handle_update() -> Result<()> {
         ));
     }
 
-    let current_version = Ver::from_str(split[1])?;
+    let current_version = split[1].parse::<SemVer>()?;
 
     // find the latest version on github in releases
-    let repo = "https://api.github.com/repos/MystenLabs/suiup/releases/latest";
-    let client = reqwest::Client::new();
-    let response = client
-        .get(repo)
-        .header("User-Agent", "suiup")
+    let response = latest_release_request(github_token.as_deref())
         .send()
         .await?
         .json::<serde_json::Value>()
@@ -132,13 +165,11 @@ pub async fn handle_update() -> Result<()> {
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Failed to parse latest version from GitHub response"))?;
 
-    let latest_version = Ver::from_str(tag)?;
+    let latest_version = tag.parse::<SemVer>()?;
 
     if current_version == latest_version {
         println!("suiup is already up to date");
         return Ok(());
-    } else {
-        println!("Updating to latest version: {}", latest_version);
     }
 
     // download the latest version from github
@@ -148,27 +179,41 @@ pub async fn handle_update() -> Result<()> {
     let url =
         format!("https://github.com/MystenLabs/suiup/releases/download/{tag}/{archive_name}",);
 
+    if dry_run {
+        let arrow = symbols().arrow;
+        println!("Would update suiup v{current_version} {arrow} v{latest_version} from {url}");
+        return Ok(());
+    }
+    println!("Updating to latest version: {}", latest_version);
+
     let temp_dir = tempfile::tempdir()?;
     let archive_path = temp_dir.path().join(&archive_name);
-    download_file(&url, &temp_dir.path().join(archive_name), "suiup", None).await?;
-
-    // extract the archive
-    let file = File::open(archive_path.as_path())
-        .map_err(|_| anyhow!("Cannot open archive file: {}", archive_path.display()))?;
-    let tar = GzDecoder::new(file);
-    let mut archive = Archive::new(tar);
-    archive
-        .unpack(temp_dir.path())
-        .map_err(|_| anyhow!("Cannot unpack archive file: {}", archive_path.display()))?;
+    download_file(
+        &url,
+        &temp_dir.path().join(archive_name),
+        "suiup",
+        github_token,
+        None,
+    )
+    .await?;
 
     #[cfg(not(windows))]
     let binary = "suiup";
     #[cfg(windows)]
     let binary = "suiup.exe";
 
-    // replace the current binary with the new one
+    // extract only the suiup binary from the archive
     let binary_path = temp_dir.path().join(binary);
-    std::fs::copy(binary_path, current_exe)?;
+    unpack_binary(&archive_path, binary, &binary_path)?;
+
+    // keep the current binary around so that `suiup self rollback` can restore it
+    let backup = backup_exe_path();
+    std::fs::copy(&current_exe, &backup)
+        .map_err(|e| anyhow!("Cannot back up suiup to {}: {e}", backup.display()))?;
+    println!("Backed up the current version to {}", backup.display());
+
+    // replace the current binary with the new one
+    replace_exe(&binary_path, &current_exe)?;
 
     println!("suiup updated to version {}", latest_version);
     // cleanup
@@ -176,6 +221,159 @@ pub async fn handle_update() -> Result<()> {
     Ok(())
 }
 
+/// Build metadata embedded at compile time by the build script
+#[derive(Debug, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub build_date: &'static str,
+    pub target: &'static str,
+    pub rustc: &'static str,
+}
+
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_sha: env!("SUIUP_GIT_SHA"),
+    build_date: env!("SUIUP_BUILD_DATE"),
+    target: env!("SUIUP_TARGET"),
+    rustc: env!("SUIUP_RUSTC"),
+};
+
+/// Handles `self check-token`, failing when GitHub rejects the token
+pub async fn handle_check_token(github_token: Option<&str>) -> Result<()> {
+    let status = check_github_token(github_token).await?;
+    if status == TokenStatus::Invalid {
+        bail!("GitHub token {status}. Fix it or unset GITHUB_TOKEN");
+    }
+    println!("GitHub token: {status}");
+    Ok(())
+}
+
+pub fn handle_version(json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(&BUILD_INFO)?);
+    } else {
+        println!("suiup {}", BUILD_INFO.version);
+        println!("git sha:    {}", BUILD_INFO.git_sha);
+        println!("build date: {}", BUILD_INFO.build_date);
+        println!("target:     {}", BUILD_INFO.target);
+        println!("rustc:      {}", BUILD_INFO.rustc);
+    }
+    Ok(())
+}
+
+/// Restores the executable backed up by the last `suiup self update`. The current executable is
+/// moved to the backup location, so running rollback again undoes the rollback.
+pub fn handle_rollback() -> Result<()> {
+    let backup = backup_exe_path();
+    if !backup.exists() {
+        bail!(
+            "No backup found at {}. A backup is created when running `suiup self update`.",
+            backup.display()
+        );
+    }
+
+    // make sure the backup is a working suiup before putting it in place
+    let output = Command::new(&backup)
+        .arg("--version")
+        .output()
+        .map_err(|e| anyhow!("Cannot run the backup at {}: {e}", backup.display()))?;
+    let backup_version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || backup_version.is_empty() {
+        bail!(
+            "The backup at {} does not run correctly, refusing to restore it",
+            backup.display()
+        );
+    }
+
+    let current_exe = replaceable_exe()?;
+    let previous = backup.with_extension("tmp");
+    std::fs::copy(&current_exe, &previous)?;
+    replace_exe(&backup, &current_exe)?;
+    std::fs::rename(&previous, &backup)?;
+
+    println!("Rolled back to {backup_version}");
+    Ok(())
+}
+
+/// The real file of the running suiup, which self update and rollback replace. Symlinks are
+/// resolved and reported, and files installed by a package manager are refused, as that package
+/// manager would not know about the change.
+fn replaceable_exe() -> Result<PathBuf> {
+    let current_exe = std::env::current_exe()?;
+    // canonical paths on windows are verbatim (`\\?\C:\...`) and never equal current_exe
+    let real_exe = match current_exe.canonicalize() {
+        Ok(real_exe) if cfg!(unix) => real_exe,
+        _ => current_exe.clone(),
+    };
+    let invoked = invoked_path().filter(|path| path.is_symlink());
+    if let Some(link) = invoked
+        .as_ref()
+        .or((real_exe != current_exe).then_some(&current_exe))
+    {
+        println!(
+            "suiup at {} is a symlink to {}, which will be replaced",
+            link.display(),
+            real_exe.display()
+        );
+    }
+    if is_package_managed(&real_exe) {
+        bail!(
+            "suiup at {} is managed by a package manager, update it with that package manager instead",
+            real_exe.display()
+        );
+    }
+    Ok(real_exe)
+}
+
+/// The path suiup was started from, as typed or found on the PATH. Unlike `current_exe`, symlinks
+/// are not resolved.
+fn invoked_path() -> Option<PathBuf> {
+    let arg0 = PathBuf::from(std::env::args_os().next()?);
+    if arg0.components().count() > 1 {
+        return Some(arg0);
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&arg0))
+        .find(|path| path.is_file())
+}
+
+/// Whether `path` is in a folder owned by the system package manager (`/usr` other than
+/// `/usr/local`, or `/opt`)
+fn is_package_managed(path: &Path) -> bool {
+    cfg!(unix)
+        && ((path.starts_with("/usr") && !path.starts_with("/usr/local"))
+            || path.starts_with("/opt"))
+}
+
+/// Location of the executable backed up by `suiup self update`
+fn backup_exe_path() -> PathBuf {
+    #[cfg(not(windows))]
+    let name = "suiup.bak";
+    #[cfg(windows)]
+    let name = "suiup.bak.exe";
+    get_suiup_data_dir().join(name)
+}
+
+/// Replaces the (possibly running) executable at `dst` with `src`. The new executable is copied
+/// next to `dst` and renamed over it, so `dst` is never missing, even if the copy fails halfway.
+/// A running executable cannot be written to on unix, but it can be replaced this way.
+fn replace_exe(src: &Path, dst: &Path) -> Result<()> {
+    let file_name = dst.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dst.with_file_name(format!(".{file_name}.suiup-new"));
+    let result = std::fs::copy(src, &tmp)
+        .map_err(|e| anyhow!("Cannot copy {} to {}: {e}", src.display(), tmp.display()))
+        .and_then(|_| {
+            #[cfg(unix)]
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o755))?;
+            std::fs::rename(&tmp, dst).map_err(|e| anyhow!("Cannot replace {}: {e}", dst.display()))
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
 pub fn handle_uninstall() -> Result<()> {
     let current_exe = std::env::current_exe()?;
     if current_exe.exists() {
@@ -189,27 +387,55 @@ pub fn handle_uninstall() -> Result<()> {
 
 fn find_archive_name() -> Result<String> {
     let (os, arch) = detect_os_arch()?;
+    Ok(archive_name_for(&os, &arch))
+}
 
-    let os = match os.as_str() {
-        "linux" => "Linux-musl",
+/// Maps the OS and architecture tokens from `detect_os_arch` to the suiup release archive name.
+fn archive_name_for(os: &str, arch: &str) -> String {
+    let os = match os {
+        "ubuntu" | "linux" => "Linux-musl",
         "windows" => "Windows",
         "macos" => "macOS",
-        _ => &os,
+        _ => os,
     };
 
-    let arch = match arch.as_str() {
+    let arch = match arch {
         "x86_64" => "x86_64",
-        "aarch64" => "arm64",
-        _ => &arch,
+        "aarch64" | "arm64" => "arm64",
+        _ => arch,
     };
 
-    let filename = if os == "Windows" && arch == "arm64" {
+    if os == "Windows" && arch == "arm64" {
         "suiup-Windows-msvc-arm64.zip".to_string()
     } else {
         format!("suiup-{os}-{arch}.tar.gz")
-    };
+    }
+}
 
-    Ok(filename)
+/// Extracts `binary` from the downloaded archive to `output_path`, leaving the other archive
+/// members out. Zip archives are handed to the system `tar`, which ships with Windows 10 and
+/// later and understands the zip format; tarballs are streamed with [`extract_archive_entry`].
+fn unpack_binary(archive_path: &Path, binary: &str, output_path: &Path) -> Result<()> {
+    if archive_path.extension().is_some_and(|ext| ext == "zip") {
+        let dest = output_path.parent().unwrap_or(Path::new("."));
+        let status = Command::new("tar")
+            .arg("-xf")
+            .arg(archive_path)
+            .arg("-C")
+            .arg(dest)
+            .arg(binary)
+            .status()
+            .map_err(|e| anyhow!("Cannot run tar to unpack {}: {e}", archive_path.display()))?;
+        if !status.success() {
+            bail!("Cannot unpack archive file: {}", archive_path.display());
+        }
+        return Ok(());
+    }
+
+    if !extract_archive_entry(archive_path, binary, output_path)? {
+        bail!("{binary} not found in archive {}", archive_path.display());
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -217,106 +443,72 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_ver_from_str_valid_versions() {
-        // Test basic version parsing
-        let v1 = Ver::from_str("1.2.3").unwrap();
-        assert_eq!(v1.major, 1);
-        assert_eq!(v1.minor, 2);
-        assert_eq!(v1.patch, 3);
-
-        // Test version with 'v' prefix
-        let v2 = Ver::from_str("v1.2.3").unwrap();
-        assert_eq!(v2.major, 1);
-        assert_eq!(v2.minor, 2);
-        assert_eq!(v2.patch, 3);
-
-        // Test zero versions
-        let v3 = Ver::from_str("0.0.0").unwrap();
-        assert_eq!(v3.major, 0);
-        assert_eq!(v3.minor, 0);
-        assert_eq!(v3.patch, 0);
-
-        // Test larger version numbers
-        let v4 = Ver::from_str("v10.20.30").unwrap();
-        assert_eq!(v4.major, 10);
-        assert_eq!(v4.minor, 20);
-        assert_eq!(v4.patch, 30);
+    fn test_replace_exe() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let src = temp_dir.path().join("new");
+        let dst = temp_dir.path().join("suiup");
+        std::fs::write(&src, "new").unwrap();
+        std::fs::write(&dst, "old").unwrap();
+
+        // a failed copy leaves the current executable in place
+        assert!(replace_exe(&temp_dir.path().join("missing"), &dst).is_err());
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "old");
+
+        replace_exe(&src, &dst).unwrap();
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), "new");
+        // no temporary file is left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
     }
 
+    #[cfg(unix)]
     #[test]
-    fn test_ver_from_str_invalid_versions() {
-        // Test invalid formats
-        assert!(Ver::from_str("1.2").is_err());
-        assert!(Ver::from_str("1.2.3.4").is_err());
-        assert!(Ver::from_str("1").is_err());
-        assert!(Ver::from_str("").is_err());
-        assert!(Ver::from_str("a.b.c").is_err());
-        assert!(Ver::from_str("1.a.3").is_err());
-        assert!(Ver::from_str("v1.2.c").is_err());
+    fn test_is_package_managed() {
+        assert!(is_package_managed(Path::new("/usr/bin/suiup")));
+        assert!(is_package_managed(Path::new(
+            "/opt/homebrew/Cellar/suiup/0.0.4/bin/suiup"
+        )));
+        assert!(!is_package_managed(Path::new("/usr/local/bin/suiup")));
+        assert!(!is_package_managed(Path::new("/home/me/.local/bin/suiup")));
+        assert!(!is_package_managed(Path::new("/usrlocal/suiup")));
     }
 
     #[test]
-    fn test_ver_equality() {
-        let v1 = Ver::from_str("1.2.3").unwrap();
-        let v2 = Ver::from_str("v1.2.3").unwrap();
-        let v3 = Ver::from_str("1.2.4").unwrap();
-
-        assert_eq!(v1, v2);
-        assert_eq!(v2, v1);
-        assert_ne!(v1, v3);
-        assert_ne!(v3, v1);
-    }
-
-    #[test]
-    fn test_ver_ordering() {
-        // Test major version differences
-        let v1_0_0 = Ver::from_str("1.0.0").unwrap();
-        let v2_0_0 = Ver::from_str("2.0.0").unwrap();
-        assert!(v1_0_0 < v2_0_0);
-        assert!(v2_0_0 > v1_0_0);
-
-        // Test minor version differences
-        let v1_1_0 = Ver::from_str("1.1.0").unwrap();
-        let v1_2_0 = Ver::from_str("1.2.0").unwrap();
-        assert!(v1_1_0 < v1_2_0);
-        assert!(v1_2_0 > v1_1_0);
-
-        // Test patch version differences
-        let v1_1_1 = Ver::from_str("1.1.1").unwrap();
-        let v1_1_2 = Ver::from_str("1.1.2").unwrap();
-        assert!(v1_1_1 < v1_1_2);
-        assert!(v1_1_2 > v1_1_1);
-
-        // Test same versions
-        let v1 = Ver::from_str("1.2.3").unwrap();
-        let v2 = Ver::from_str("v1.2.3").unwrap();
-        assert!(v1 <= v2);
-        assert!(v1 >= v2);
-        assert!(!(v1 < v2));
-        assert!(!(v1 > v2));
-
-        // Test complex comparisons
-        let v0_0_4 = Ver::from_str("0.0.4").unwrap();
-        let v0_0_3 = Ver::from_str("0.0.3").unwrap();
-        assert!(v0_0_3 < v0_0_4);
-        assert!(v0_0_4 > v0_0_3);
-
-        // Test the specific case from the bug report
-        let current = Ver::from_str("0.0.4").unwrap();
-        let latest = Ver::from_str("0.0.3").unwrap();
-        assert!(!(current < latest)); // Current is newer, should not show warning
-        assert!(latest < current); // Latest is older than current
+    fn test_update_status() {
+        let version = |v: &str| v.parse::<SemVer>().unwrap();
+        let (message, available) = update_status(&version("0.0.4"), &version("v0.0.5"));
+        assert!(available);
+        assert!(message.contains("v0.0.4") && message.contains("v0.0.5"));
+
+        let (message, available) = update_status(&version("0.0.5"), &version("v0.0.5"));
+        assert!(!available);
+        assert_eq!(
+            message,
+            "suiup v0.0.5 is up to date (latest release: v0.0.5)"
+        );
+        assert!(!update_status(&version("0.0.6"), &version("v0.0.5")).1);
     }
 
     #[test]
-    fn test_ver_display() {
-        let v1 = Ver::from_str("1.2.3").unwrap();
-        assert_eq!(format!("{}", v1), "1.2.3");
-
-        let v2 = Ver::from_str("v10.20.30").unwrap();
-        assert_eq!(format!("{}", v2), "10.20.30");
-
-        let v3 = Ver::from_str("0.0.0").unwrap();
-        assert_eq!(format!("{}", v3), "0.0.0");
+    fn test_archive_name_for_targets() {
+        assert_eq!(
+            archive_name_for("ubuntu", "x86_64"),
+            "suiup-Linux-musl-x86_64.tar.gz"
+        );
+        assert_eq!(
+            archive_name_for("ubuntu", "aarch64"),
+            "suiup-Linux-musl-arm64.tar.gz"
+        );
+        assert_eq!(
+            archive_name_for("macos", "arm64"),
+            "suiup-macOS-arm64.tar.gz"
+        );
+        assert_eq!(
+            archive_name_for("windows", "x86_64"),
+            "suiup-Windows-x86_64.tar.gz"
+        );
+        assert_eq!(
+            archive_name_for("windows", "arm64"),
+            "suiup-Windows-msvc-arm64.zip"
+        );
     }
 }
@@ -2,49 +2,353 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    handlers::installed_binaries_grouped_by_network,
+    commands::BinaryName,
+    handlers::{
+        installed_binaries_grouped_by_network,
+        release::{ensure_version_prefix, last_release_for_network, release_list},
+        version::compare_versions,
+    },
     paths::default_file_path,
-    types::{Binaries, Version},
+    standalone::StandaloneInstaller,
+    symbols::symbols,
+    types::{Binaries, BinaryVersion, NetworkOrBranch, Version},
 };
-use anyhow::Error;
+use anyhow::{bail, Error};
 use std::collections::BTreeMap;
 
-use crate::commands::print_table;
+use crate::commands::format_table;
+use crate::handlers::pager::page_output;
 
-/// Load default binaries from configuration file
-fn load_default_binaries() -> Result<Binaries, Error> {
+/// Default binaries as stored in the default version file: name -> (network, version, debug)
+type DefaultVersions = BTreeMap<String, (String, Version, bool)>;
+
+fn load_default_versions() -> Result<DefaultVersions, Error> {
     let default = std::fs::read_to_string(default_file_path()?)?;
-    let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&default)?;
-    Ok(Binaries::from(default))
+    Ok(serde_json::from_str(&default)?)
+}
+
+/// Load default binaries from configuration file
+pub fn load_default_binaries() -> Result<Binaries, Error> {
+    Ok(Binaries::from(load_default_versions()?))
 }
 
 /// Load installed binaries grouped by network
 fn load_installed_binaries() -> Result<Vec<crate::types::BinaryVersion>, Error> {
     let installed_binaries = installed_binaries_grouped_by_network(None)?;
     let binaries = installed_binaries
-        .into_iter()
-        .flat_map(|(_, binaries)| binaries.to_owned())
+        .into_values()
+        .flat_map(|binaries| binaries.to_owned())
         .collect();
     Ok(binaries)
 }
 
-/// Display a section with title and binaries table
-fn display_binaries_section(title: &str, binaries: &Vec<crate::types::BinaryVersion>) {
-    println!("\x1b[1m{}:\x1b[0m", title);
-    print_table(binaries);
+/// Formats a section with title and binaries table, with the release dates when `verbose`. See
+/// [`format_table`] for `defaults`.
+fn format_binaries_section(
+    title: &str,
+    binaries: &[crate::types::BinaryVersion],
+    verbose: bool,
+    defaults: bool,
+) -> String {
+    format!(
+        "\x1b[1m{}:\x1b[0m\n{}\n",
+        title,
+        format_table(binaries, verbose, defaults)
+    )
+}
+
+/// Copies the release date of the matching installed binary onto each default one, as the
+/// default version file does not record it
+fn add_release_dates(defaults: &mut [BinaryVersion], installed: &[BinaryVersion]) {
+    for default in defaults {
+        let name = default
+            .binary_name
+            .strip_suffix("-debug")
+            .filter(|_| default.debug)
+            .unwrap_or(&default.binary_name);
+        default.released_at = installed
+            .iter()
+            .find(|b| {
+                b.binary_name == name
+                    && b.network_release == default.network_release
+                    && b.version == default.version
+                    && b.debug == default.debug
+            })
+            .and_then(|b| b.released_at.clone());
+    }
+}
+
+/// Formats installed binaries as a tree of binary, network and versions. Versions keep the order
+/// of `installed_binaries_grouped_by_network` (newest last) and the default one is marked by `*`.
+fn format_tree(
+    installed: BTreeMap<String, Vec<BinaryVersion>>,
+    defaults: &DefaultVersions,
+) -> String {
+    let mut tree: BTreeMap<String, BTreeMap<String, Vec<BinaryVersion>>> = BTreeMap::new();
+    for (network, binaries) in installed {
+        for binary in binaries {
+            tree.entry(binary.binary_name.clone())
+                .or_default()
+                .entry(network.clone())
+                .or_default()
+                .push(binary);
+        }
+    }
+
+    let mut out = String::new();
+    for (name, networks) in tree {
+        out.push_str(&format!("{name}\n"));
+        for (network, binaries) in networks {
+            out.push_str(&format!("  {network}\n"));
+            for binary in binaries {
+                // install records a debug default under the binary name, `default set --debug`
+                // under `<name>-debug`
+                let is_default = [name.clone(), format!("{name}-debug")]
+                    .iter()
+                    .filter_map(|key| defaults.get(key))
+                    .any(|(n, v, d)| {
+                        *n == binary.network_release && *v == binary.version && *d == binary.debug
+                    });
+                let marker = if is_default { "*" } else { " " };
+                let debug = if binary.debug { " (debug)" } else { "" };
+                out.push_str(&format!(
+                    "    {marker} {}{debug}\n",
+                    binary.display_version()
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// The newest installed version of each binary, per network or `standalone` release. Nightly
+/// builds and binaries downloaded for another platform are left out, as no release replaces them.
+fn newest_installed(installed: &[BinaryVersion]) -> BTreeMap<(String, String), String> {
+    let mut newest: BTreeMap<(String, String), String> = BTreeMap::new();
+    for binary in installed {
+        if binary.target.is_some() || binary.version == "nightly" {
+            continue;
+        }
+        let version = ensure_version_prefix(&binary.version);
+        newest
+            .entry((binary.binary_name.clone(), binary.network_release.clone()))
+            .and_modify(|newest| {
+                if compare_versions(&version, newest).is_gt() {
+                    *newest = version.clone();
+                }
+            })
+            .or_insert(version);
+    }
+    newest
+}
+
+/// Handles `show --outdated`: lists the installed binaries that have a newer release, and fails
+/// when there is any
+pub async fn handle_show_outdated(github_token: Option<String>) -> Result<(), Error> {
+    let mut outdated = 0;
+    for ((name, network), current) in newest_installed(&load_installed_binaries()?) {
+        // only the binaries suiup manages have releases to compare with
+        let Ok(binary) = name.parse::<BinaryName>() else {
+            continue;
+        };
+        let latest = if binary.is_standalone() {
+            StandaloneInstaller::new(binary.repo())
+                .latest_version()
+                .await?
+        } else if let NetworkOrBranch::Network(_) = NetworkOrBranch::from(network.as_str()) {
+            // release lists are cached per repository, so each one is fetched once
+            let (releases, _) = release_list(&binary.repo(), github_token.clone()).await?;
+            match last_release_for_network(&releases, &network).await {
+                Ok((_, version)) => ensure_version_prefix(&version),
+                Err(e) => {
+                    eprintln!("[{}] Cannot check {name} ({network}): {e}", symbols().warn);
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+        if compare_versions(&latest, &current).is_gt() {
+            println!("{name} ({network}): {current} -> {latest}");
+            outdated += 1;
+        }
+    }
+    if outdated > 0 {
+        bail!("{outdated} installed binaries have a newer release. Use `suiup update <binary>` to update them");
+    }
+    println!("All installed binaries are up to date");
+    Ok(())
+}
+
+/// Keeps only the debug builds with `Some(true)`, only the other ones with `Some(false)`
+fn retain_debug(binaries: &mut Vec<BinaryVersion>, debug: Option<bool>) {
+    if let Some(debug) = debug {
+        binaries.retain(|binary| binary.debug == debug);
+    }
 }
 
-/// Handles the `show` command
-pub fn handle_show(default_only: bool) -> Result<(), Error> {
+/// Handles the `show` command. `debug` filters the binaries shown, see [`retain_debug`].
+pub fn handle_show(
+    default_only: bool,
+    tree: bool,
+    debug: Option<bool>,
+    no_pager: bool,
+    verbose: bool,
+) -> Result<(), Error> {
+    if tree {
+        let mut installed = installed_binaries_grouped_by_network(None)?;
+        for binaries in installed.values_mut() {
+            retain_debug(binaries, debug);
+        }
+        installed.retain(|_, binaries| !binaries.is_empty());
+        if installed.is_empty() {
+            println!("No binaries installed");
+            return Ok(());
+        }
+        return page_output(&format_tree(installed, &load_default_versions()?), no_pager);
+    }
+
     // Load and display default binaries
-    let default_binaries = load_default_binaries()?;
-    display_binaries_section("Default binaries", &default_binaries.binaries);
+    let mut default_binaries = load_default_binaries()?;
+    let mut installed_binaries = load_installed_binaries()?;
+    retain_debug(&mut default_binaries.binaries, debug);
+    retain_debug(&mut installed_binaries, debug);
+    if verbose {
+        add_release_dates(&mut default_binaries.binaries, &installed_binaries);
+    }
+    let mut output = format_binaries_section(
+        "Default binaries",
+        &default_binaries.binaries,
+        verbose,
+        true,
+    );
 
     // Only show installed binaries if --default flag is not set
     if !default_only {
-        let installed_binaries = load_installed_binaries()?;
-        display_binaries_section("Installed binaries", &installed_binaries);
+        output.push_str(&format_binaries_section(
+            "Installed binaries",
+            &installed_binaries,
+            verbose,
+            false,
+        ));
     }
 
-    Ok(())
+    page_output(&output, no_pager)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(name: &str, network: &str, version: &str, debug: bool) -> BinaryVersion {
+        BinaryVersion {
+            binary_name: name.to_string(),
+            network_release: network.to_string(),
+            version: version.to_string(),
+            debug,
+            path: None,
+            alias: None,
+            installed_at: None,
+            target: None,
+            released_at: None,
+            companions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_verbose_section_shows_release_dates() {
+        let mut installed = binary("sui", "testnet", "v1.40.1", false);
+        installed.released_at = Some("2025-01-20T10:00:00Z".to_string());
+        let mut defaults = vec![binary("sui", "testnet", "v1.40.1", false)];
+        add_release_dates(&mut defaults, &[installed]);
+        assert_eq!(
+            defaults[0].released_at.as_deref(),
+            Some("2025-01-20T10:00:00Z")
+        );
+
+        let section = format_binaries_section("Default binaries", &defaults, true, true);
+        assert!(section.contains("Released"));
+        assert!(section.contains("2025-01-20T10:00:00Z"));
+        assert!(
+            !format_binaries_section("Default binaries", &defaults, false, true)
+                .contains("Released")
+        );
+    }
+
+    #[test]
+    fn test_newest_installed() {
+        let mut other_platform = binary("sui", "devnet", "v1.41.0", false);
+        other_platform.target = Some("macos-aarch64".to_string());
+        let installed = [
+            binary("sui", "testnet", "v1.40.1", false),
+            binary("sui", "testnet", "1.39.3", false),
+            binary("sui", "devnet", "v1.40.0", false),
+            binary("sui", "main", "nightly", false),
+            binary("mvr", "standalone", "v0.0.8", false),
+            other_platform,
+        ];
+        let key = |name: &str, network: &str| (name.to_string(), network.to_string());
+        assert_eq!(
+            newest_installed(&installed),
+            BTreeMap::from([
+                (key("mvr", "standalone"), "v0.0.8".to_string()),
+                (key("sui", "devnet"), "v1.40.0".to_string()),
+                (key("sui", "testnet"), "v1.40.1".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_format_tree_marks_defaults() {
+        let installed = BTreeMap::from([
+            (
+                "devnet".to_string(),
+                vec![binary("sui", "devnet", "v1.40.0", false)],
+            ),
+            (
+                "testnet".to_string(),
+                vec![
+                    binary("sui", "testnet", "v1.39.3", false),
+                    binary("sui", "testnet", "v1.40.1", false),
+                    binary("sui", "testnet", "v1.40.1", true),
+                    binary("walrus", "testnet", "v1.18.2", false),
+                ],
+            ),
+        ]);
+        let defaults = BTreeMap::from([
+            (
+                "sui".to_string(),
+                ("testnet".to_string(), "v1.40.1".to_string(), false),
+            ),
+            (
+                "walrus".to_string(),
+                ("mainnet".to_string(), "v1.18.2".to_string(), false),
+            ),
+        ]);
+
+        assert_eq!(
+            format_tree(installed.clone(), &defaults),
+            "sui\n  devnet\n      v1.40.0\n  testnet\n      v1.39.3\n    * v1.40.1\n      v1.40.1 (debug)\nwalrus\n  testnet\n      v1.18.2\n"
+        );
+
+        // `install --debug` records the debug default under the binary name
+        let defaults = BTreeMap::from([(
+            "sui".to_string(),
+            ("testnet".to_string(), "v1.40.1".to_string(), true),
+        )]);
+        assert_eq!(
+            format_tree(installed.clone(), &defaults),
+            "sui\n  devnet\n      v1.40.0\n  testnet\n      v1.39.3\n      v1.40.1\n    * v1.40.1 (debug)\nwalrus\n  testnet\n      v1.18.2\n"
+        );
+
+        // `default set --debug` records it as `sui-debug`
+        let defaults = BTreeMap::from([(
+            "sui-debug".to_string(),
+            ("testnet".to_string(), "v1.40.1".to_string(), true),
+        )]);
+        assert_eq!(
+            format_tree(installed, &defaults),
+            "sui\n  devnet\n      v1.40.0\n  testnet\n      v1.39.3\n      v1.40.1\n    * v1.40.1 (debug)\nwalrus\n  testnet\n      v1.18.2\n"
+        );
+    }
 }
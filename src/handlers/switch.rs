@@ -2,29 +2,65 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, bail, Result};
-use tracing::info;
+
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
-    handlers::update_default_version_file,
-    paths::{binaries_dir, get_default_bin_dir},
-    types::{BinaryVersion, InstalledBinaries},
+    handlers::{
+        batch::BatchResult, binary_source_path, confirm_mainnet, place_companions,
+        place_default_binary, release::ensure_version_prefix, remember_network,
+        update_default_version_file, version::compare_versions,
+    },
+    paths::{default_file_path, get_default_bin_dir},
+    symbols::symbols,
+    types::{BinaryVersion, InstalledBinaries, NetworkOrBranch, Version},
 };
 
-#[cfg(not(windows))]
-use std::os::unix::fs::PermissionsExt;
+/// Handle the switch command. Every spec is processed even if an earlier one fails, and the
+/// command errors out at the end with the number of specs that could not be switched.
+///
+/// When `version` is set, that exact installed version is used instead of the latest one.
+pub fn handle_switch(binary_specs: &[String], version: Option<&str>) -> Result<()> {
+    let installed_binaries = InstalledBinaries::new()?;
+    let mut batch = BatchResult::new("switch", true);
 
-/// Handle the switch command
-pub fn handle_switch(binary_spec: &str) -> Result<()> {
-    // Parse the binary@network_release format
-    let (binary_name, network_release) = parse_binary_spec(binary_spec)?;
+    for binary_spec in binary_specs {
+        batch.record(
+            binary_spec,
+            switch_spec(&installed_binaries, binary_spec, version),
+        );
+    }
+
+    if batch.len() > 1 {
+        println!("Switched {} of {} binaries", batch.succeeded(), batch.len());
+    }
+
+    batch.finish()
+}
+
+/// Switch a single binary@network_release spec
+fn switch_spec(
+    installed_binaries: &InstalledBinaries,
+    binary_spec: &str,
+    version: Option<&str>,
+) -> Result<()> {
+    // Parse the binary@network_release[-version] format
+    let (binary_name, network_release, spec_version) = parse_binary_spec(binary_spec)?;
+    let version = match (spec_version.as_deref(), version) {
+        (Some(spec_version), Some(version)) if spec_version != ensure_version_prefix(version) => {
+            bail!("Conflicting versions {spec_version} and --version {version}")
+        }
+        (Some(spec_version), _) => Some(spec_version),
+        (None, version) => version,
+    };
 
     // Find the matching installed binary
-    let installed_binaries = InstalledBinaries::new()?;
     let matching_binary =
-        find_matching_binary(&installed_binaries, &binary_name, &network_release)?;
+        find_matching_binary(installed_binaries, &binary_name, &network_release, version)?;
 
     // Switch to the found binary
     switch_to_binary(&matching_binary)?;
+    remember_network(&binary_name, &network_release)?;
 
     println!(
         "Successfully switched to {}-{} from {}",
@@ -34,38 +70,67 @@ pub fn handle_switch(binary_spec: &str) -> Result<()> {
     Ok(())
 }
 
-/// Parse binary@network_release format
-pub fn parse_binary_spec(spec: &str) -> Result<(String, String)> {
+/// Parse binary@network_release format, optionally followed by an exact version, e.g.
+/// `sui@testnet` or `sui@testnet-v1.39.3`. The version is returned with a `v` prefix.
+pub fn parse_binary_spec(spec: &str) -> Result<(String, String, Option<String>)> {
     let parts: Vec<&str> = spec.split('@').collect();
 
     if parts.len() != 2 {
         bail!(
-            "Invalid format. Use 'binary@network_release' format (e.g., 'sui@testnet', 'mvr@main')"
+            "Invalid format. Use 'binary@network_release' or 'binary@network_release-version' format (e.g., 'sui@testnet', 'sui@testnet-v1.39.3', 'mvr@main')"
         );
     }
 
     let binary_name = parts[0].to_string();
-    let network_release = parts[1].to_string();
+    let mut network_release = parts[1];
+    let mut version = None;
+
+    // branch names can contain dashes too, so only a trailing semver is taken as the version
+    if let Some((network, suffix)) = network_release.rsplit_once('-') {
+        if suffix.is_empty() {
+            bail!("Version cannot be empty. Use e.g. '{binary_name}@{network}-v1.39.3'");
+        }
+        if is_version(suffix) {
+            network_release = network;
+            version = Some(ensure_version_prefix(suffix));
+        }
+    }
 
     if binary_name.is_empty() || network_release.is_empty() {
         bail!("Binary name and network/release cannot be empty");
     }
 
-    Ok((binary_name, network_release))
+    Ok((binary_name, network_release.to_string(), version))
 }
 
-/// Find the matching binary from installed binaries
+/// Checks whether `s` is a `1.39.3` or `v1.39.3` style version
+fn is_version(s: &str) -> bool {
+    let parts: Vec<&str> = s.strip_prefix('v').unwrap_or(s).split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Find the matching binary from installed binaries. If `version` is `None`, the latest
+/// installed version for the network/release is returned.
 pub fn find_matching_binary(
     installed_binaries: &InstalledBinaries,
     binary_name: &str,
     network_release: &str,
+    version: Option<&str>,
 ) -> Result<BinaryVersion> {
     let binaries = installed_binaries.binaries();
 
-    // Find all matching binaries for the given binary name and network/release
+    // Find all matching binaries for the given binary name and network/release, leaving out
+    // binaries downloaded for another platform
     let mut matching_binaries: Vec<&BinaryVersion> = binaries
         .iter()
-        .filter(|b| b.binary_name == binary_name && b.network_release == network_release)
+        .filter(|b| {
+            b.binary_name == binary_name
+                && b.network_release == network_release
+                && b.target.is_none()
+        })
         .collect();
 
     if matching_binaries.is_empty() {
@@ -76,23 +141,106 @@ pub fn find_matching_binary(
         );
     }
 
-    // Sort by version to get the latest one (this is a simple string sort, might need improvement)
-    matching_binaries.sort_by(|a, b| b.version.cmp(&a.version));
+    if let Some(version) = version {
+        // nightly builds are recorded without the `v` prefix
+        let prefixed = ensure_version_prefix(version);
+        return matching_binaries
+            .into_iter()
+            .find(|b| b.version == version || b.version == prefixed)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Version {prefixed} of {binary_name}@{network_release} is not installed. Use 'suiup show' to see available binaries."
+                )
+            });
+    }
+
+    // Sort by version to get the latest one
+    matching_binaries.sort_by(|a, b| compare_versions(&b.version, &a.version));
 
     Ok(matching_binaries[0].clone())
 }
 
-/// Switch to the specified binary by copying it to the default bin directory
-fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
-    let src = get_binary_source_path(binary);
-    let dst = get_binary_destination_path(binary);
+/// Sets the default of every installed binary to its newest installed version on the network (and
+/// build type) of its current default. Binaries without a default and nightly defaults, whose
+/// builds have no comparable version, are skipped.
+pub fn handle_set_all_latest(yes: bool) -> Result<()> {
+    let installed_binaries = InstalledBinaries::new()?;
+    let default = std::fs::read_to_string(default_file_path()?)?;
+    let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&default)?;
+
+    let names: BTreeSet<&str> = installed_binaries
+        .binaries()
+        .iter()
+        .map(|b| b.binary_name.as_str())
+        .collect();
+
+    let (mut updated, mut up_to_date, mut skipped) = (0, 0, 0);
+    let mut batch = BatchResult::new("update", true);
+    for name in names {
+        let Some((network, version, debug)) = default.get(name) else {
+            println!("{name}: no default set, skipped");
+            skipped += 1;
+            continue;
+        };
+        if version == "nightly" {
+            println!("{name}: nightly build from {network}, skipped");
+            skipped += 1;
+            continue;
+        }
+        let Some(latest) = installed_binaries
+            .binaries()
+            .iter()
+            .filter(|b| {
+                b.binary_name == name
+                    && b.network_release == *network
+                    && b.debug == *debug
+                    && b.target.is_none()
+            })
+            .max_by(|a, b| compare_versions(&a.version, &b.version))
+        else {
+            println!("{name}: no {network} version installed, skipped");
+            skipped += 1;
+            continue;
+        };
+        if compare_versions(&latest.version, version).is_le() {
+            println!("{name}: {version} from {network} is already the latest");
+            up_to_date += 1;
+            continue;
+        }
+
+        let result = confirm_mainnet(network, "switch to a mainnet binary", yes)
+            .and_then(|_| switch_to_binary(latest));
+        if result.is_ok() {
+            println!(
+                "{name}: {version} {} {} from {network}",
+                symbols().arrow,
+                latest.version
+            );
+            updated += 1;
+        }
+        batch.record(name, result);
+    }
 
-    // Copy the binary file
-    copy_binary_file(&src, &dst, &binary.binary_name)?;
+    println!("Updated {updated} defaults, {up_to_date} already latest, {skipped} skipped");
+    batch.finish()
+}
 
-    // Set executable permissions on Unix systems
-    #[cfg(unix)]
-    set_executable_permissions(&dst)?;
+/// Switch to the specified binary by copying it to the default bin directory, or by repointing
+/// the default binary when it is a symlink, see [`place_default_binary`]
+fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
+    let src = binary_source_path(binary);
+    if !src.exists() {
+        bail!(
+            "{}-{} is registered but its file is missing; run '{}'",
+            binary.binary_name,
+            binary.version,
+            reinstall_command(binary)
+        );
+    }
+    let dst = get_binary_destination_path(binary);
+    place_default_binary(&src, &dst, &binary.binary_name)?;
+    place_companions(binary)?;
 
     // Update the default version file
     update_default_version_file(
@@ -105,28 +253,24 @@ fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
     Ok(())
 }
 
-/// Construct the source path for a binary
-fn get_binary_source_path(binary: &BinaryVersion) -> std::path::PathBuf {
-    let mut src = binaries_dir();
-    src.push(&binary.network_release);
-
-    // Handle nightly builds which have a different directory structure
-    if binary.version == "nightly" {
-        src.push("bin");
+/// The install command that brings back the file of a registered binary
+fn reinstall_command(binary: &BinaryVersion) -> String {
+    let BinaryVersion {
+        binary_name: name,
+        version,
+        ..
+    } = binary;
+    let debug = if binary.debug { " --debug" } else { "" };
+    let version = version.strip_prefix('v').unwrap_or(version);
+    match binary.network() {
+        NetworkOrBranch::Branch(branch) => {
+            format!("suiup install {name} --nightly {branch}{debug}")
+        }
+        NetworkOrBranch::Standalone => format!("suiup install {name}@{version}{debug}"),
+        NetworkOrBranch::Network(network) => {
+            format!("suiup install {name}@{network}-{version}{debug}")
+        }
     }
-
-    let binary_filename = if binary.debug {
-        format!("{}-debug-{}", binary.binary_name, binary.version)
-    } else {
-        format!("{}-{}", binary.binary_name, binary.version)
-    };
-
-    src.push(&binary_filename);
-
-    #[cfg(target_os = "windows")]
-    src.set_extension("exe");
-
-    src
 }
 
 /// Construct the destination path for a binary
@@ -145,35 +289,3 @@ fn get_binary_destination_path(binary: &BinaryVersion) -> std::path::PathBuf {
 
     dst
 }
-
-/// Copy binary file from source to destination
-fn copy_binary_file(src: &std::path::Path, dst: &std::path::Path, binary_name: &str) -> Result<()> {
-    info!("Copying from {} to {}", src.display(), dst.display());
-
-    // Remove existing file if it exists
-    if dst.exists() {
-        std::fs::remove_file(dst)?;
-    }
-
-    // Copy the binary
-    std::fs::copy(src, dst).map_err(|e| {
-        anyhow!(
-            "Error copying {} to the default folder (src: {}, dst: {}): {}",
-            binary_name,
-            src.display(),
-            dst.display(),
-            e
-        )
-    })?;
-
-    Ok(())
-}
-
-/// Set executable permissions on Unix systems
-#[cfg(unix)]
-fn set_executable_permissions(path: &std::path::Path) -> Result<()> {
-    let mut perms = std::fs::metadata(path)?.permissions();
-    perms.set_mode(0o755);
-    std::fs::set_permissions(path, perms)?;
-    Ok(())
-}
@@ -4,12 +4,13 @@
 use crate::{
     commands::{parse_component_with_version, BinaryName, CommandMetadata, ComponentCommands},
     handle_commands::handle_cmd,
-    types::InstalledBinaries,
+    types::{Config, InstalledBinaries},
 };
 use crate::{
     handlers::{
         available_components, installed_binaries_grouped_by_network,
-        release::{last_release_for_network, release_list},
+        release::{ensure_version_prefix, last_release_for_network, release_list},
+        version::compare_versions,
     },
     types::Repo,
 };
@@ -18,6 +19,7 @@ use anyhow::{bail, Error};
 /// Handles the `update` command
 pub async fn handle_update(
     binary_name: String,
+    network: Option<String>,
     yes: bool,
     github_token: Option<String>,
 ) -> Result<(), Error> {
@@ -25,12 +27,26 @@ pub async fn handle_update(
         bail!("Invalid number of arguments for `update` command");
     }
 
-    let CommandMetadata { name, version, .. } = parse_component_with_version(&binary_name)?;
+    let CommandMetadata {
+        name,
+        version,
+        network: spec_network,
+    } = parse_component_with_version(&binary_name, &Config::load()?)?;
 
     if version.is_some() {
         bail!("Update should be done without a version. Use `suiup install` to specify a version");
     }
 
+    // `sui@devnet` names the network as well as `--network devnet` does
+    let network = match (network, binary_name.trim() != name.to_str()) {
+        (Some(network), true) if network != spec_network => {
+            bail!("Conflicting networks: {binary_name} and --network {network}")
+        }
+        (Some(network), _) => Some(network),
+        (None, true) => Some(spec_network),
+        (None, false) => None,
+    };
+
     if !available_components().contains(&name.to_str()) {
         bail!("Invalid component name: {}", name);
     }
@@ -42,7 +58,8 @@ pub async fn handle_update(
     }
     let binaries_by_network = installed_binaries_grouped_by_network(Some(installed_binaries))?;
 
-    let mut network_local_last_version: Vec<(String, String)> = vec![];
+    // network, last version known locally and its `--also` companions
+    let mut network_local_last_version: Vec<(String, String, Vec<String>)> = vec![];
 
     for (network, binaries) in &binaries_by_network {
         let last_version = binaries
@@ -55,25 +72,48 @@ pub async fn handle_update(
         let last_version = if last_version.len() > 1 {
             last_version
                 .iter()
-                .max_by(|a, b| a.version.cmp(&b.version))
+                .max_by(|a, b| compare_versions(&a.version, &b.version))
                 .unwrap()
         } else {
             last_version.first().unwrap()
         };
-        network_local_last_version.push((network.clone(), last_version.version.clone()));
+        network_local_last_version.push((
+            network.clone(),
+            last_version.version.clone(),
+            last_version.companions.clone(),
+        ));
     }
     // map of network and last version known locally
 
+    if let Some(network) = &network {
+        network_local_last_version.retain(|(n, _, _)| n == network);
+        if network_local_last_version.is_empty() {
+            bail!("{name} is not installed for {network}. Use `suiup show` to see installed binaries and `suiup install {name}@{network}` to install it.");
+        }
+    }
+    // a network given on the command line is updated in place, not the default network
+    let component = match &network {
+        Some(network) => format!("{name}@{network}"),
+        None => binary_name.clone(),
+    };
+
     // find the last local version of the name binary, for each network
     // then find the last release for each network and compare the versions
 
     if name == BinaryName::Mvr {
+        // mvr has a single standalone release line
         handle_cmd(
             ComponentCommands::Add {
                 component: binary_name,
                 debug: false,
                 nightly: None,
                 yes,
+                binary_name: None,
+                checksum: None,
+                also: vec![],
+                target: None,
+                no_default: false,
+                version_file: None,
             },
             github_token,
         )
@@ -84,10 +124,16 @@ pub async fn handle_update(
     if name == BinaryName::Walrus {
         handle_cmd(
             ComponentCommands::Add {
-                component: binary_name,
+                component,
                 debug: false,
                 nightly: None,
                 yes,
+                binary_name: None,
+                checksum: None,
+                also: vec![],
+                target: None,
+                no_default: false,
+                version_file: None,
             },
             github_token,
         )
@@ -97,25 +143,34 @@ pub async fn handle_update(
 
     let releases = release_list(&Repo::Sui, github_token.clone()).await?.0;
     let mut to_update = vec![];
-    for (n, v) in &network_local_last_version {
+    for (n, v, companions) in &network_local_last_version {
         let last_release = last_release_for_network(&releases, n).await?;
-        let last_version = last_release.1;
-        if v == &last_version {
+        let last_version = ensure_version_prefix(&last_release.1);
+        let v = ensure_version_prefix(v);
+        // a local build newer than the latest release is not downgraded
+        if compare_versions(&v, &last_version).is_ge() {
             println!("[{n} release] {name} is up to date");
         } else {
             println!("[{n} release] {name} is outdated. Local: {v}, Latest: {last_version}");
-            to_update.push((n, last_version));
+            to_update.push((n, last_version, companions));
         }
     }
 
-    for (n, v) in to_update.iter() {
+    // the companions are updated together with their binary
+    for (n, v, companions) in to_update {
         println!("Updating {name} to {v} from {n} release");
         handle_cmd(
             ComponentCommands::Add {
-                component: binary_name.clone(),
+                component: format!("{name}@{n}"),
                 debug: false,
                 nightly: None,
                 yes,
+                binary_name: None,
+                checksum: None,
+                also: companions.clone(),
+                target: None,
+                no_default: false,
+                version_file: None,
             },
             github_token.clone(),
         )
@@ -1,8 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{anyhow, Error};
+use anyhow::{anyhow, bail, Error};
 use lazy_static::lazy_static;
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 lazy_static! {
     static ref VERSION_REGEX: regex::Regex = regex::Regex::new(r"v\d+\.\d+\.\d+").unwrap();
@@ -16,3 +22,302 @@ pub fn extract_version_from_release(release: &str) -> Result<String, Error> {
 
     Ok(captures.get(0).unwrap().as_str().to_string())
 }
+
+/// A `major.minor.patch` version, ordered numerically so `1.10.0` is newer than `1.9.0`. Parsing
+/// accepts an optional `v` prefix, and it is displayed without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl SemVer {
+    /// Parses a version such as `v1.40.1` or `1.40.1-abc` as well as it can: parts that are
+    /// missing or not numeric count as 0, and anything after a `-` is ignored. Used to order
+    /// installed versions, which are never rejected.
+    pub fn lenient(version: &str) -> SemVer {
+        let mut parts = version
+            .trim_start_matches('v')
+            .split(['.', '-'])
+            .map(|p| p.parse::<u64>().unwrap_or(0));
+        SemVer {
+            major: parts.next().unwrap_or(0),
+            minor: parts.next().unwrap_or(0),
+            patch: parts.next().unwrap_or(0),
+        }
+    }
+}
+
+impl FromStr for SemVer {
+    type Err = Error;
+
+    /// Parses exactly three numeric parts, with an optional `v` prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .strip_prefix('v')
+            .unwrap_or(s)
+            .split('.')
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| anyhow!("Invalid version format: {s}"))?;
+        let [major, minor, patch] = parts[..] else {
+            bail!("Invalid version format: {s}");
+        };
+        Ok(SemVer {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl Display for SemVer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Compares two versions numerically, ignoring whether they have a `v` prefix, so `v1.10.0` is
+/// newer than `1.9.0`.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    SemVer::lenient(a).cmp(&SemVer::lenient(b))
+}
+
+/// Looks for a `.<binary>-version` file (e.g. `.sui-version`) in `start` and its parents, and
+/// returns its path and the version spec it contains (e.g. `testnet-1.40.1`). Blank lines and
+/// `#` comments are ignored.
+pub fn find_version_file(start: &Path, binary: &str) -> Result<Option<(PathBuf, String)>, Error> {
+    let file_name = format!(".{binary}-version");
+    for dir in start.ancestors() {
+        let path = dir.join(&file_name);
+        if !path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let spec = content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .find(|line| !line.is_empty())
+            .ok_or_else(|| anyhow!("Version file {} is empty", path.display()))?;
+        return Ok(Some((path, spec.to_string())));
+    }
+    Ok(None)
+}
+
+/// Returns the version spec pinned for `binary` by a `.<binary>-version` file in the current
+/// directory or one of its parents, like `.nvmrc`
+pub fn read_version_file(binary: &str) -> Result<Option<String>, Error> {
+    let Some((path, spec)) = find_version_file(&std::env::current_dir()?, binary)? else {
+        return Ok(None);
+    };
+    println!("Using {binary}@{spec} from {}", path.display());
+    Ok(Some(spec))
+}
+
+/// Time a binary gets to print its version
+pub const VERSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs `cmd` (a binary with `--version`) and returns the first line it prints. Fails if the
+/// binary cannot be run, does not exit within `timeout`, exits with an error or prints nothing.
+pub fn reported_version(cmd: Command, timeout: Duration) -> Result<String, String> {
+    let output = run_with_timeout(cmd, timeout)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reported = stdout.lines().next().unwrap_or_default().trim().to_string();
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    if reported.is_empty() {
+        return Err("did not report a version".to_string());
+    }
+    Ok(reported)
+}
+
+/// Whether the version line a binary printed matches the `recorded` version. Nightly builds
+/// match any version.
+pub fn version_matches(reported: &str, recorded: &str) -> bool {
+    let recorded = recorded.trim_start_matches('v');
+    recorded == "nightly" || reported.contains(recorded)
+}
+
+/// Runs `cmd` and collects its output, killing it if it does not exit within `timeout`
+fn run_with_timeout(mut cmd: Command, timeout: Duration) -> Result<Output, String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("could not be run: {e}"))?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return child.wait_with_output().map_err(|e| e.to_string()),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("did not exit within {}s", timeout.as_secs_f32()));
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches("sui 1.40.1-abc", "v1.40.1"));
+        assert!(version_matches("sui 1.41.0-abc", "nightly"));
+        assert!(!version_matches("sui 1.39.3", "v1.40.1"));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("v1.40.1", "1.40.1"), Ordering::Equal);
+        assert_eq!(compare_versions("v1.10.0", "v1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.39.3", "v1.40.1"), Ordering::Less);
+        assert_eq!(
+            SemVer::lenient("v1.40.1-abc"),
+            SemVer {
+                major: 1,
+                minor: 40,
+                patch: 1
+            }
+        );
+        assert_eq!(SemVer::lenient("nightly"), SemVer::lenient("0.0.0"));
+    }
+
+    #[test]
+    fn test_find_version_file_walks_up() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert!(find_version_file(&nested, "sui").unwrap().is_none());
+
+        std::fs::write(
+            root.path().join(".sui-version"),
+            "# pinned for the team\ntestnet-1.40.1\n",
+        )
+        .unwrap();
+        let (path, spec) = find_version_file(&nested, "sui").unwrap().unwrap();
+        assert_eq!(path, root.path().join(".sui-version"));
+        assert_eq!(spec, "testnet-1.40.1");
+        assert!(find_version_file(&nested, "walrus").unwrap().is_none());
+
+        // the closest file wins
+        std::fs::write(nested.join(".sui-version"), "devnet").unwrap();
+        let (_, spec) = find_version_file(&nested, "sui").unwrap().unwrap();
+        assert_eq!(spec, "devnet");
+
+        std::fs::write(nested.join(".sui-version"), "\n# nothing\n").unwrap();
+        assert!(find_version_file(&nested, "sui").is_err());
+    }
+
+    #[test]
+    fn test_semver_from_str_valid_versions() {
+        // Test basic version parsing
+        let v1 = "1.2.3".parse::<SemVer>().unwrap();
+        assert_eq!(v1.major, 1);
+        assert_eq!(v1.minor, 2);
+        assert_eq!(v1.patch, 3);
+
+        // Test version with 'v' prefix
+        let v2 = "v1.2.3".parse::<SemVer>().unwrap();
+        assert_eq!(v2.major, 1);
+        assert_eq!(v2.minor, 2);
+        assert_eq!(v2.patch, 3);
+
+        // Test zero versions
+        let v3 = "0.0.0".parse::<SemVer>().unwrap();
+        assert_eq!(v3.major, 0);
+        assert_eq!(v3.minor, 0);
+        assert_eq!(v3.patch, 0);
+
+        // Test larger version numbers
+        let v4 = "v10.20.30".parse::<SemVer>().unwrap();
+        assert_eq!(v4.major, 10);
+        assert_eq!(v4.minor, 20);
+        assert_eq!(v4.patch, 30);
+    }
+
+    #[test]
+    fn test_semver_from_str_invalid_versions() {
+        // Test invalid formats
+        assert!("1.2".parse::<SemVer>().is_err());
+        assert!("1.2.3.4".parse::<SemVer>().is_err());
+        assert!("1".parse::<SemVer>().is_err());
+        assert!("".parse::<SemVer>().is_err());
+        assert!("a.b.c".parse::<SemVer>().is_err());
+        assert!("1.a.3".parse::<SemVer>().is_err());
+        assert!("v1.2.c".parse::<SemVer>().is_err());
+    }
+
+    #[test]
+    fn test_semver_equality() {
+        let v1 = "1.2.3".parse::<SemVer>().unwrap();
+        let v2 = "v1.2.3".parse::<SemVer>().unwrap();
+        let v3 = "1.2.4".parse::<SemVer>().unwrap();
+
+        assert_eq!(v1, v2);
+        assert_eq!(v2, v1);
+        assert_ne!(v1, v3);
+        assert_ne!(v3, v1);
+    }
+
+    #[test]
+    #[allow(clippy::nonminimal_bool)]
+    fn test_semver_ordering() {
+        // Test major version differences
+        let v1_0_0 = "1.0.0".parse::<SemVer>().unwrap();
+        let v2_0_0 = "2.0.0".parse::<SemVer>().unwrap();
+        assert!(v1_0_0 < v2_0_0);
+        assert!(v2_0_0 > v1_0_0);
+
+        // Test minor version differences
+        let v1_1_0 = "1.1.0".parse::<SemVer>().unwrap();
+        let v1_2_0 = "1.2.0".parse::<SemVer>().unwrap();
+        assert!(v1_1_0 < v1_2_0);
+        assert!(v1_2_0 > v1_1_0);
+
+        // Test patch version differences
+        let v1_1_1 = "1.1.1".parse::<SemVer>().unwrap();
+        let v1_1_2 = "1.1.2".parse::<SemVer>().unwrap();
+        assert!(v1_1_1 < v1_1_2);
+        assert!(v1_1_2 > v1_1_1);
+
+        // Test same versions
+        let v1 = "1.2.3".parse::<SemVer>().unwrap();
+        let v2 = "v1.2.3".parse::<SemVer>().unwrap();
+        assert!(v1 <= v2);
+        assert!(v1 >= v2);
+        assert!(!(v1 < v2));
+        assert!(!(v1 > v2));
+
+        // Test complex comparisons
+        let v0_0_4 = "0.0.4".parse::<SemVer>().unwrap();
+        let v0_0_3 = "0.0.3".parse::<SemVer>().unwrap();
+        assert!(v0_0_3 < v0_0_4);
+        assert!(v0_0_4 > v0_0_3);
+
+        // Test the specific case from the bug report
+        let current = "0.0.4".parse::<SemVer>().unwrap();
+        let latest = "0.0.3".parse::<SemVer>().unwrap();
+        assert!(!(current < latest)); // Current is newer, should not show warning
+        assert!(latest < current); // Latest is older than current
+    }
+
+    #[test]
+    fn test_semver_display() {
+        let v1 = "1.2.3".parse::<SemVer>().unwrap();
+        assert_eq!(format!("{}", v1), "1.2.3");
+
+        let v2 = "v10.20.30".parse::<SemVer>().unwrap();
+        assert_eq!(format!("{}", v2), "10.20.30");
+
+        let v3 = "0.0.0".parse::<SemVer>().unwrap();
+        assert_eq!(format!("{}", v3), "0.0.0");
+    }
+}
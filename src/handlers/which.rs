@@ -1,12 +1,75 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::paths::get_default_bin_dir;
+use crate::handlers::is_in_path;
+use crate::paths::{default_file_path, display_path, get_default_bin_dir};
+use crate::symbols::symbols;
+use crate::types::{InstalledBinaries, Version};
 use anyhow::Error;
+use clap::ValueEnum;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Output format of the `which` command
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum WhichFormat {
+    /// The folder the default binaries are installed to
+    #[default]
+    Path,
+    /// A `<NAME>_BIN=<path>` line per default binary and per binary installed with
+    /// `--binary-name`, for `.env` files or `eval`
+    Env,
+}
 
 /// Handles the `which` command
-pub fn handle_which() -> Result<(), Error> {
+pub fn handle_which(format: WhichFormat) -> Result<(), Error> {
     let default_bin = get_default_bin_dir();
-    println!("{}", default_bin.display());
+    if format == WhichFormat::Path {
+        println!("{}", display_path(&default_bin));
+        if !is_in_path(&default_bin) {
+            eprintln!(
+                "[{}] {} is not in your PATH",
+                symbols().warn,
+                display_path(&default_bin)
+            );
+        }
+        return Ok(());
+    }
+
+    let default = std::fs::read_to_string(default_file_path()?)?;
+    let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&default)?;
+    // debug defaults are keyed by their file name, e.g. `sui-debug`
+    let mut names: BTreeSet<String> = default.into_keys().collect();
+    // binaries installed with `--binary-name` sit in the default folder under that name
+    names.extend(
+        InstalledBinaries::new()?
+            .binaries()
+            .iter()
+            .filter_map(|b| b.alias.clone()),
+    );
+    for name in &names {
+        let path = default_bin.join(name);
+        #[cfg(windows)]
+        let path = path.with_extension("exe");
+        if path.is_file() {
+            println!("{}={}", env_var_name(name), display_path(&path));
+        }
+    }
     Ok(())
 }
+
+/// The variable a default binary is exported as, e.g. `SITE_BUILDER_BIN` for `site-builder`
+pub fn env_var_name(binary: &str) -> String {
+    format!("{}_BIN", binary.to_uppercase().replace('-', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(env_var_name("sui"), "SUI_BIN");
+        assert_eq!(env_var_name("sui-debug"), "SUI_DEBUG_BIN");
+        assert_eq!(env_var_name("site-builder"), "SITE_BUILDER_BIN");
+    }
+}
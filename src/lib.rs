@@ -3,10 +3,12 @@
 
 pub mod commands;
 pub mod component;
+mod date;
 pub mod handle_commands;
 pub mod handlers;
 pub mod paths;
 pub mod standalone;
+pub mod symbols;
 pub mod types;
 
 #[cfg(feature = "nix-patchelf")]
@@ -2,18 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
+use std::time::Duration;
 use suiup::commands::Command;
-use suiup::paths::initialize;
+use suiup::handlers::batch::BatchError;
+use suiup::handlers::release::wait_for_refreshes;
+use suiup::handlers::self_::UpdateAvailable;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    env_logger::init();
-    initialize()?;
-
     let cmd = Command::parse();
-    if let Err(err) = cmd.exec().await {
+
+    let mut logger = env_logger::Builder::from_default_env();
+    if cmd.verbose {
+        // only suiup's own debug output, not that of every dependency
+        logger.filter_module("suiup", log::LevelFilter::Debug);
+    }
+    logger.init();
+
+    let result = cmd.exec().await;
+    // give the release lists refreshed in the background by --offline-first a moment to be
+    // saved, a refresh still running afterwards is dropped and retried by the next invocation
+    wait_for_refreshes(Duration::from_millis(300)).await;
+
+    if let Err(err) = result {
+        // `self update --check` already told about the update, only the exit code is left
+        if let Some(update) = err.downcast_ref::<UpdateAvailable>() {
+            std::process::exit(update.exit_code());
+        }
         eprintln!("Error: {}", err);
-        std::process::exit(1);
+        // batch commands exit with the number of items that failed
+        let code = err
+            .downcast_ref::<BatchError>()
+            .map_or(1, BatchError::exit_code);
+        std::process::exit(code);
     }
 
     Ok(())
@@ -86,7 +86,10 @@ pub fn patch_binary(binary_path: &Path) -> Result<()> {
             ));
         }
 
-        println!("✓ Binary patched successfully");
+        println!(
+            "{} Binary patched successfully",
+            crate::symbols::symbols().ok
+        );
         println!("  Interpreter: {}", deps.interpreter);
         println!("  RPATH: {}", deps.lib_path);
 
@@ -1,12 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
+use clap::ValueEnum;
 use std::collections::BTreeMap;
 use std::env;
 use std::fs::{create_dir_all, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::handlers::RELEASES_ARCHIVES_FOLDER;
 use crate::types::InstalledBinaries;
@@ -20,119 +22,158 @@ const XDG_CACHE_HOME: &str = "XDG_CACHE_HOME";
 #[cfg(not(windows))]
 const HOME: &str = "HOME";
 
-pub fn get_data_home() -> PathBuf {
+/// Returns the home directory the default folders are derived from: `SUIUP_HOME` if set,
+/// otherwise `HOME` (`USERPROFILE` on Windows)
+pub fn resolve_home() -> Result<PathBuf, Error> {
+    #[cfg(windows)]
+    let vars = ["SUIUP_HOME", "USERPROFILE"];
+    #[cfg(not(windows))]
+    let vars = ["SUIUP_HOME", HOME];
+
+    vars.into_iter()
+        .filter_map(env::var_os)
+        .find(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Cannot determine home directory; set HOME or SUIUP_HOME"))
+}
+
+/// `initialize` resolves the suiup folders before anything else runs, so their infallible getters
+/// below only fail if it was skipped
+const HOME_CHECKED: &str = "home directory is checked by initialize";
+
+/// Returns `var` as a path, falling back to `default` under the home directory
+fn env_dir_or_home(var: &str, default: &[&str]) -> Result<PathBuf, Error> {
+    match env::var_os(var) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(default.iter().fold(resolve_home()?, |path, p| path.join(p))),
+    }
+}
+
+fn data_home() -> Result<PathBuf, Error> {
     #[cfg(windows)]
     {
-        env::var_os("LOCALAPPDATA")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut home =
-                    PathBuf::from(env::var_os("USERPROFILE").expect("USERPROFILE not set"));
-                home.push("AppData");
-                home.push("Local");
-                home
-            })
+        env_dir_or_home("LOCALAPPDATA", &["AppData", "Local"])
     }
 
     #[cfg(not(windows))]
     {
-        env::var_os(XDG_DATA_HOME)
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut home = PathBuf::from(env::var_os(HOME).expect("HOME not set"));
-                home.push(".local");
-                home.push("share");
-                home
-            })
+        env_dir_or_home(XDG_DATA_HOME, &[".local", "share"])
     }
 }
 
-pub fn get_config_home() -> PathBuf {
+fn config_home() -> Result<PathBuf, Error> {
     #[cfg(windows)]
     {
-        env::var_os("LOCALAPPDATA")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut home =
-                    PathBuf::from(env::var_os("USERPROFILE").expect("USERPROFILE not set"));
-                home.push("AppData");
-                home.push("Local");
-                home
-            })
+        env_dir_or_home("LOCALAPPDATA", &["AppData", "Local"])
     }
 
     #[cfg(not(windows))]
     {
-        env::var_os(XDG_CONFIG_HOME)
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut home = PathBuf::from(env::var_os("HOME").expect("HOME not set"));
-                home.push(".config");
-                home
-            })
+        env_dir_or_home(XDG_CONFIG_HOME, &[".config"])
     }
 }
 
-pub fn get_cache_home() -> PathBuf {
+fn cache_home() -> Result<PathBuf, Error> {
     #[cfg(windows)]
     {
-        env::var_os("TEMP").map(PathBuf::from).unwrap_or_else(|| {
-            let mut home = PathBuf::from(env::var_os("USERPROFILE").expect("USERPROFILE not set"));
-            home.push("AppData");
-            home.push("Local");
-            home.push("Temp");
-            home
-        })
+        env_dir_or_home("TEMP", &["AppData", "Local", "Temp"])
     }
 
     #[cfg(not(windows))]
     {
-        env::var_os(XDG_CACHE_HOME)
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut home = PathBuf::from(env::var_os("HOME").expect("HOME not set"));
-                home.push(".cache");
-                home
-            })
+        env_dir_or_home(XDG_CACHE_HOME, &[".cache"])
     }
 }
 
-pub fn get_suiup_data_dir() -> PathBuf {
-    get_data_home().join("suiup")
+/// The platform data home. `initialize` does not resolve it when `--data-dir` is given, so unlike
+/// the suiup folders it can fail.
+pub fn get_data_home() -> Result<PathBuf, Error> {
+    data_home()
 }
 
-pub fn get_suiup_config_dir() -> PathBuf {
-    get_config_home().join("suiup")
+/// The platform config home, see [`get_data_home`]
+pub fn get_config_home() -> Result<PathBuf, Error> {
+    config_home()
 }
 
-pub fn get_suiup_cache_dir() -> PathBuf {
-    get_cache_home().join("suiup")
+/// The platform cache home, see [`get_data_home`]
+pub fn get_cache_home() -> Result<PathBuf, Error> {
+    cache_home()
 }
 
-pub fn get_default_bin_dir() -> PathBuf {
+/// Directories set with `--data-dir`, `--cache-dir` and `--config-dir`, replacing the suiup
+/// folders in the platform data, cache and config homes
+#[derive(Clone, Debug, Default)]
+pub struct DirOverrides {
+    pub data_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub config_dir: Option<PathBuf>,
+}
+
+static DIR_OVERRIDES: OnceLock<DirOverrides> = OnceLock::new();
+
+/// Sets the directory overrides for the rest of the command. Only the first call has an effect.
+pub fn set_dir_overrides(overrides: DirOverrides) {
+    let _ = DIR_OVERRIDES.set(overrides);
+}
+
+fn dir_override(dir: impl Fn(&DirOverrides) -> &Option<PathBuf>) -> Option<PathBuf> {
+    DIR_OVERRIDES.get().and_then(|o| dir(o).clone())
+}
+
+fn suiup_data_dir() -> Result<PathBuf, Error> {
+    match dir_override(|o| &o.data_dir) {
+        Some(dir) => Ok(dir),
+        None => Ok(data_home()?.join("suiup")),
+    }
+}
+
+fn suiup_config_dir() -> Result<PathBuf, Error> {
+    match dir_override(|o| &o.config_dir) {
+        Some(dir) => Ok(dir),
+        None => Ok(config_home()?.join("suiup")),
+    }
+}
+
+fn suiup_cache_dir() -> Result<PathBuf, Error> {
+    match dir_override(|o| &o.cache_dir) {
+        Some(dir) => Ok(dir),
+        None => Ok(cache_home()?.join("suiup")),
+    }
+}
+
+fn default_bin_dir() -> Result<PathBuf, Error> {
     #[cfg(windows)]
     {
-        let mut path = PathBuf::from(env::var_os("LOCALAPPDATA").expect("LOCALAPPDATA not set"));
-        path.push("bin");
-        if !path.exists() {
-            std::fs::create_dir_all(&path).unwrap();
-        }
-        path
+        let path = env_dir_or_home("LOCALAPPDATA", &["AppData", "Local"])?.join("bin");
+        create_dir_all(&path)?;
+        Ok(path)
     }
 
     #[cfg(not(windows))]
     {
-        env::var_os("SUIUP_DEFAULT_BIN_DIR")
-            .map(PathBuf::from)
-            .unwrap_or_else(|| {
-                let mut path = PathBuf::from(env::var_os(HOME).expect("HOME not set"));
-                path.push(".local");
-                path.push("bin");
-                path
-            })
+        env_dir_or_home("SUIUP_DEFAULT_BIN_DIR", &[".local", "bin"])
     }
 }
 
+pub fn get_suiup_data_dir() -> PathBuf {
+    suiup_data_dir().expect(HOME_CHECKED)
+}
+
+pub fn get_suiup_config_dir() -> PathBuf {
+    suiup_config_dir().expect(HOME_CHECKED)
+}
+
+pub fn get_suiup_cache_dir() -> PathBuf {
+    suiup_cache_dir().expect(HOME_CHECKED)
+}
+
+/// Folder the default binaries are copied to. The optional `active_dir` of the config file holds
+/// symlinks to the same binaries instead, see [`crate::handlers::active::sync_active_dir`].
+pub fn get_default_bin_dir() -> PathBuf {
+    default_bin_dir().expect(HOME_CHECKED)
+}
+
 pub fn get_config_file(name: &str) -> PathBuf {
     get_suiup_config_dir().join(name)
 }
@@ -159,6 +200,11 @@ pub fn installed_binaries_file() -> Result<PathBuf, Error> {
     Ok(path)
 }
 
+/// Returns the path to the optional suiup settings file
+pub fn config_file_path() -> PathBuf {
+    get_config_file("config.json")
+}
+
 pub fn release_archive_dir() -> PathBuf {
     get_suiup_cache_dir().join(RELEASES_ARCHIVES_FOLDER)
 }
@@ -169,13 +215,57 @@ pub fn binaries_dir() -> PathBuf {
 }
 
 pub fn initialize() -> Result<(), Error> {
-    create_dir_all(get_suiup_config_dir())?;
-    create_dir_all(get_suiup_data_dir())?;
-    create_dir_all(get_suiup_cache_dir())?;
+    create_dir_all(suiup_config_dir()?)?;
+    create_dir_all(suiup_data_dir()?)?;
+    create_dir_all(suiup_cache_dir()?)?;
     create_dir_all(binaries_dir())?;
     create_dir_all(release_archive_dir())?;
-    create_dir_all(get_default_bin_dir())?;
+    create_dir_all(default_bin_dir()?)?;
     default_file_path()?;
     installed_binaries_file()?;
     Ok(())
 }
+
+/// How paths are printed for the user to copy into their shell configuration
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PathStyle {
+    /// Paths as the OS reports them, e.g. `C:\Users\x\bin` on Windows
+    #[default]
+    Native,
+    /// POSIX style paths for Git Bash/WSL/MSYS, e.g. `/c/Users/x/bin`
+    Posix,
+}
+
+static PATH_STYLE: OnceLock<PathStyle> = OnceLock::new();
+
+/// Sets the path style used by `display_path`. Only the first call has an effect.
+pub fn set_path_style(style: PathStyle) {
+    let _ = PATH_STYLE.set(style);
+}
+
+/// Returns the path style selected with `--path-style`, defaulting to native paths
+pub fn path_style() -> PathStyle {
+    PATH_STYLE.get().copied().unwrap_or_default()
+}
+
+/// Formats a path for printing according to the selected path style
+pub fn display_path(path: &Path) -> String {
+    match path_style() {
+        PathStyle::Native => path.display().to_string(),
+        PathStyle::Posix => to_posix(path),
+    }
+}
+
+/// Converts a Windows path to POSIX style: `C:\Users\x\bin` becomes `/c/Users/x/bin` and
+/// `\\server\share` becomes `//server/share`. Paths that are already POSIX style are returned as
+/// they are.
+pub fn to_posix(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut chars = path.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(drive), Some(':'), None | Some('/')) if drive.is_ascii_alphabetic() => {
+            format!("/{}{}", drive.to_ascii_lowercase(), &path[2..])
+        }
+        _ => path,
+    }
+}
@@ -3,8 +3,10 @@
 
 // use crate::handle_commands::{binaries_folder, detect_os_arch, download_file};
 use crate::{
-    handlers::download::{detect_os_arch, download_file},
-    paths::binaries_dir,
+    handlers::{
+        download::{download_file, match_asset_pattern, target_os_arch, Checksum},
+        installed_binary_path,
+    },
     types::Repo,
 };
 use anyhow::{anyhow, Error};
@@ -14,6 +16,8 @@ use serde::Deserialize;
 pub struct StandaloneRelease {
     pub tag_name: String,
     pub assets: Vec<StandaloneAsset>,
+    #[serde(default)]
+    pub published_at: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -62,9 +66,26 @@ impl StandaloneInstaller {
             .ok_or_else(|| anyhow!("No {} releases found", self.repo.binary_name()))
     }
 
-    /// Download the CLI binary, if it does not exist in the binary folder.
-    pub async fn download_version(&mut self, version: Option<String>) -> Result<String, Error> {
-        let version = if let Some(v) = version {
+    /// The tag of the latest release, fetching the release list if needed
+    pub async fn latest_version(&mut self) -> Result<String, Error> {
+        self.get_releases().await?;
+        self.releases
+            .first()
+            .map(|release| release.tag_name.clone())
+            .ok_or_else(|| anyhow!("No {} releases found", self.repo.binary_name()))
+    }
+
+    /// When the release of `version` was published, if the release list was fetched
+    pub fn published_at(&self, version: &str) -> Option<String> {
+        self.releases
+            .iter()
+            .find(|r| r.tag_name == version)
+            .and_then(|r| r.published_at.clone())
+    }
+
+    /// The release tag of `version`, or of the latest release when none is given
+    async fn resolve_version(&mut self, version: Option<String>) -> Result<String, Error> {
+        Ok(if let Some(v) = version {
             // Ensure version has 'v' prefix for GitHub release tags
             crate::handlers::release::ensure_version_prefix(&v)
         } else {
@@ -74,18 +95,58 @@ impl StandaloneInstaller {
             let latest_release = self.get_latest_release()?.tag_name.clone();
             println!("No version specified. Downloading latest release: {latest_release}");
             latest_release
+        })
+    }
+
+    /// Finds the asset of the release of `version` (the latest one when none is given) for this
+    /// machine, without downloading it. Returns the release tag and the asset.
+    pub async fn find_asset(
+        &mut self,
+        version: Option<String>,
+    ) -> Result<(String, &StandaloneAsset), Error> {
+        let version = self.resolve_version(version).await?;
+        self.get_releases().await?;
+        let asset = self.release_asset(&version)?;
+        Ok((version, asset))
+    }
+
+    /// The asset of the release of `version` for this machine, from the fetched release list
+    fn release_asset(&self, version: &str) -> Result<&StandaloneAsset, Error> {
+        let release = self
+            .releases
+            .iter()
+            .find(|r| r.tag_name == version)
+            .ok_or_else(|| anyhow!("Version {} not found", version))?;
+
+        let (os, arch) = target_os_arch(self.repo, None)?;
+        let naming = self.repo.asset_naming();
+        let names: Vec<&str> = release.assets.iter().map(|a| a.name.as_str()).collect();
+        let name = match match_asset_pattern(&names) {
+            Some(name) => Some(name?),
+            None => naming.find(&names, &os, &arch),
         };
+        name.and_then(|name| release.assets.iter().find(|a| a.name == name))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No compatible binary found for your system: {os}-{arch} (expected {})",
+                    naming.asset_name(&os, &arch, "standalone", version)
+                )
+            })
+    }
+
+    /// Download the CLI binary, if it does not exist in the binary folder.
+    pub async fn download_version(
+        &mut self,
+        version: Option<String>,
+        checksum: Option<&Checksum>,
+    ) -> Result<String, Error> {
+        let version = self.resolve_version(version).await?;
 
-        let cache_folder = binaries_dir().join("standalone");
-        if !cache_folder.exists() {
-            std::fs::create_dir_all(&cache_folder)?;
-        }
-        #[cfg(not(windows))]
-        let standalone_binary_path =
-            cache_folder.join(format!("{}-{}", self.repo.binary_name(), version));
-        #[cfg(target_os = "windows")]
         let standalone_binary_path =
-            cache_folder.join(format!("{}-{}.exe", self.repo.binary_name(), version));
+            installed_binary_path(self.repo.binary_name(), "standalone", &version);
+        if let Some(folder) = standalone_binary_path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
 
         if standalone_binary_path.exists() {
             println!("Binary {}-{version} already installed. Use `suiup default set standalone {version}` to set the default version to the desired one", self.repo.binary_name());
@@ -95,36 +156,14 @@ impl StandaloneInstaller {
         if self.releases.is_empty() {
             self.get_releases().await?;
         }
-
-        let release = self
-            .releases
-            .iter()
-            .find(|r| r.tag_name == version)
-            .ok_or_else(|| anyhow!("Version {} not found", version))?;
-
-        let (os, arch) = detect_os_arch()?;
-        let asset_name = format!("{}-{}-{}", self.repo.binary_name(), os, arch);
-
-        #[cfg(target_os = "windows")]
-        let asset_name = format!("{}.exe", asset_name);
-
-        let asset = release
-            .assets
-            .iter()
-            .find(|a| a.name.starts_with(&asset_name))
-            .ok_or_else(|| {
-                anyhow!(
-                    "No compatible binary found for your system: {}-{}",
-                    os,
-                    arch
-                )
-            })?;
+        let asset = self.release_asset(&version)?;
 
         download_file(
             &asset.browser_download_url,
             &standalone_binary_path,
             format!("{}-{version}", self.repo.binary_name()).as_str(),
             None,
+            checksum,
         )
         .await?;
 
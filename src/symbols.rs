@@ -0,0 +1,54 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Symbols used in suiup's output. Some Windows terminals and log aggregators cannot render
+//! emoji and arrows, so `--ascii` (or `TERM=dumb`) swaps them for plain ASCII.
+
+use std::sync::OnceLock;
+
+/// The symbols printed in status lines
+#[derive(Debug, PartialEq, Eq)]
+pub struct Symbols {
+    pub ok: &'static str,
+    pub fail: &'static str,
+    pub warn: &'static str,
+    pub notice: &'static str,
+    pub arrow: &'static str,
+}
+
+pub const UNICODE: Symbols = Symbols {
+    ok: "✓",
+    fail: "✗",
+    warn: "!",
+    notice: "⚠️ ",
+    arrow: "→",
+};
+
+pub const ASCII: Symbols = Symbols {
+    ok: "OK",
+    fail: "FAIL",
+    warn: "!",
+    notice: "WARN",
+    arrow: "->",
+};
+
+static ASCII_MODE: OnceLock<bool> = OnceLock::new();
+
+/// Selects ASCII output when `ascii` is set or the terminal is dumb. Only the first call has an
+/// effect.
+pub fn set_ascii(ascii: bool) {
+    let _ = ASCII_MODE.set(ascii || term_is_dumb());
+}
+
+fn term_is_dumb() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// Returns the symbol set for the selected output mode
+pub fn symbols() -> &'static Symbols {
+    if *ASCII_MODE.get_or_init(term_is_dumb) {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
@@ -13,11 +13,18 @@ use std::{
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-use crate::paths::{default_file_path, installed_binaries_file};
+use crate::date::format_date;
+use crate::paths::{config_file_path, default_file_path, installed_binaries_file};
 
 pub type Version = String;
 
-#[derive(Debug)]
+/// Network used when neither the spec nor the config name one
+pub const DEFAULT_NETWORK: &str = "testnet";
+
+/// Networks a spec can name, as opposed to branches and other releases
+pub const NETWORKS: [&str; 3] = ["testnet", "devnet", "mainnet"];
+
+#[derive(Clone, Copy, Debug)]
 pub enum Repo {
     Sui,
     Mvr,
@@ -51,6 +58,9 @@ impl Display for Repo {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Release {
     pub assets: Vec<Asset>,
+    /// When the release was published on GitHub, e.g. `2024-06-01T12:00:00Z`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -68,6 +78,41 @@ pub struct DefaultBinaries {
     pub binaries: Vec<BinaryVersion>,
 }
 
+/// Optional settings read from `config.json` in the suiup config folder
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Config {
+    /// Ask for confirmation before installing, switching to or setting a mainnet binary as
+    /// default, unless `-y` is passed
+    pub confirm_mainnet: bool,
+    /// Network used for a binary when a spec does not name one (e.g. `sui` or `sui@1.40.1`),
+    /// remembered from the last install or switch that did
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub default_network_for: BTreeMap<String, String>,
+    /// How installed binaries are laid out in the binaries folder
+    pub binary_layout: BinaryLayout,
+    /// Answer yes to every confirmation, as if `-y` was passed to each command
+    pub assume_yes: bool,
+    /// Names for binary specs (e.g. `my-stable` for `sui@testnet-1.39.3`), usable wherever a spec
+    /// is expected
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Folder kept holding only symlinks to the default binaries, to be put first in `PATH`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_dir: Option<PathBuf>,
+}
+
+/// Layout of the binaries folder
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryLayout {
+    /// `<network>/<name>-<version>`, with nightly builds in `<branch>/bin`
+    #[default]
+    Nested,
+    /// `<name>-<network>-<version>`, all in the binaries folder itself
+    Flat,
+}
+
 /// Struct to store the installed binaries
 #[derive(Serialize, Deserialize, Debug)]
 pub struct InstalledBinaries {
@@ -86,6 +131,44 @@ pub struct BinaryVersion {
     pub debug: bool,
     /// Path to the binary
     pub path: Option<String>,
+    /// Custom name under which the binary is available in the default bin folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Unix timestamp (in seconds) of when the binary was installed or last rebuilt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<u64>,
+    /// Platform the binary was downloaded for with `--target` (e.g. `linux-x86_64`), when it is
+    /// not the host one. Such binaries are never made the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// When the GitHub release the binary was installed from was published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub released_at: Option<String>,
+    /// Companion binaries extracted from the same release archive with `install --also` (e.g.
+    /// `sui-tool`). They are made the default, switched and removed together with this binary.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companions: Vec<String>,
+}
+
+impl BinaryVersion {
+    /// The network, `standalone` release or branch this binary was installed from
+    pub fn network(&self) -> NetworkOrBranch {
+        NetworkOrBranch::from(self.network_release.as_str())
+    }
+
+    /// Returns the version for display. Nightly builds all share the `nightly` version, so they
+    /// include their branch and build date, e.g. `nightly (main, built 2024-06-01)`.
+    pub fn display_version(&self) -> String {
+        match self.installed_at {
+            Some(installed_at) if self.version == "nightly" => format!(
+                "{} ({}, built {})",
+                self.version,
+                self.network_release,
+                format_date(installed_at)
+            ),
+            _ => self.version.clone(),
+        }
+    }
 }
 
 #[derive(
@@ -101,6 +184,17 @@ pub enum Network {
     Mainnet,
 }
 
+/// What a `network_release` string names: one of the networks, the `standalone` releases (e.g.
+/// mvr), or a branch a nightly build was compiled from. Unlike [`Network`], every string parses, so
+/// it round-trips through the installed binaries file unchanged.
+#[derive(Deserialize, Serialize, Hash, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum NetworkOrBranch {
+    Network(Network),
+    Standalone,
+    Branch(String),
+}
+
 impl InstalledBinaries {
     pub fn create_file(path: &PathBuf) -> Result<(), Error> {
         let binaries = InstalledBinaries { binaries: vec![] };
@@ -135,10 +229,32 @@ impl InstalledBinaries {
         Ok(binaries)
     }
 
-    /// Add a binary to the installed binaries JSON file
+    /// Add a binary to the installed binaries JSON file. Re-adding an installed binary with an
+    /// alias records the alias on the existing entry.
     pub fn add_binary(&mut self, binary: BinaryVersion) {
-        if !self.binaries.iter().any(|b| b == &binary) {
-            self.binaries.push(binary);
+        let existing = self.binaries.iter_mut().find(|b| {
+            b.binary_name == binary.binary_name
+                && b.network_release == binary.network_release
+                && b.version == binary.version
+                && b.debug == binary.debug
+                && b.path == binary.path
+        });
+        match existing {
+            Some(b) => {
+                if binary.alias.is_some() {
+                    b.alias = binary.alias;
+                }
+                // a rebuilt nightly keeps its entry, but records the new build time
+                if binary.installed_at.is_some() {
+                    b.installed_at = binary.installed_at;
+                }
+                for companion in binary.companions {
+                    if !b.companions.contains(&companion) {
+                        b.companions.push(companion);
+                    }
+                }
+            }
+            None => self.binaries.push(binary),
         }
     }
 
@@ -147,12 +263,50 @@ impl InstalledBinaries {
         self.binaries.retain(|b| b.binary_name != binary);
     }
 
+    /// Remove a single installed version of a binary from the installed binaries JSON file
+    pub fn remove_binary_version(&mut self, binary: &BinaryVersion) {
+        self.binaries.retain(|b| b != binary);
+    }
+
+    /// Remove all binaries of a network release or branch from the installed binaries JSON file
+    pub fn remove_network(&mut self, network: &str) {
+        self.binaries.retain(|b| b.network_release != network);
+    }
+
     /// List the binaries in the installed binaries JSON file
     pub fn binaries(&self) -> &[BinaryVersion] {
         &self.binaries
     }
 }
 
+impl Config {
+    /// Loads the settings file, falling back to the defaults when it does not exist
+    pub fn load() -> Result<Self, Error> {
+        let path = config_file_path();
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Cannot parse config file {}: {e}", path.display()))
+    }
+
+    /// Writes the settings back to the settings file
+    pub fn save(&self) -> Result<(), Error> {
+        std::fs::write(config_file_path(), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the network to use for `binary` when a spec does not name one: the remembered
+    /// network for that binary, otherwise testnet
+    pub fn default_network(&self, binary: &str) -> &str {
+        self.default_network_for
+            .get(binary)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_NETWORK)
+    }
+}
+
 impl DefaultBinaries {
     pub fn _load() -> Result<DefaultBinaries, Error> {
         let default_file_path = default_file_path()?;
@@ -169,10 +323,11 @@ impl Display for Binaries {
         let mut s: BTreeMap<String, Vec<(String, String, bool)>> = BTreeMap::new();
 
         for b in self.binaries.clone() {
+            let version = b.display_version();
             if let Some(binaries) = s.get_mut(&b.network_release) {
-                binaries.push((b.binary_name, b.version, b.debug));
+                binaries.push((b.binary_name, version, b.debug));
             } else {
-                s.insert(b.network_release, vec![(b.binary_name, b.version, b.debug)]);
+                s.insert(b.network_release, vec![(b.binary_name, version, b.debug)]);
             }
         }
 
@@ -192,11 +347,14 @@ impl Display for Binaries {
 
 impl Display for BinaryVersion {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.binary_name, self.version)?;
         if self.debug {
-            write!(f, "{}-{} (debug build)", self.binary_name, self.version)
-        } else {
-            write!(f, "{}-{}", self.binary_name, self.version)
+            write!(f, " (debug build)")?;
         }
+        if let Some(target) = &self.target {
+            write!(f, " ({target})")?;
+        }
+        Ok(())
     }
 }
 
@@ -210,6 +368,48 @@ impl Display for Network {
     }
 }
 
+impl Display for NetworkOrBranch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NetworkOrBranch::Network(network) => write!(f, "{network}"),
+            NetworkOrBranch::Standalone => write!(f, "standalone"),
+            NetworkOrBranch::Branch(branch) => write!(f, "{branch}"),
+        }
+    }
+}
+
+impl From<&str> for NetworkOrBranch {
+    fn from(s: &str) -> Self {
+        match s {
+            "standalone" => NetworkOrBranch::Standalone,
+            _ => s
+                .parse::<Network>()
+                .map(NetworkOrBranch::Network)
+                .unwrap_or_else(|_| NetworkOrBranch::Branch(s.to_string())),
+        }
+    }
+}
+
+impl From<String> for NetworkOrBranch {
+    fn from(s: String) -> Self {
+        NetworkOrBranch::from(s.as_str())
+    }
+}
+
+impl From<NetworkOrBranch> for String {
+    fn from(network: NetworkOrBranch) -> Self {
+        network.to_string()
+    }
+}
+
+impl FromStr for NetworkOrBranch {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetworkOrBranch::from(s))
+    }
+}
+
 impl From<BTreeMap<String, (String, Version, bool)>> for Binaries {
     fn from(map: BTreeMap<String, (String, Version, bool)>) -> Self {
         let binaries = map
@@ -220,6 +420,11 @@ impl From<BTreeMap<String, (String, Version, bool)>> for Binaries {
                 version: v.1.to_string(),
                 debug: v.2,
                 path: None,
+                alias: None,
+                installed_at: None,
+                target: None,
+                released_at: None,
+                companions: vec![],
             })
             .collect();
         Binaries { binaries }
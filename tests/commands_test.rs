@@ -6,15 +6,25 @@ mod tests {
     use anyhow::Result;
     use std::fs;
     use std::time::{Duration, SystemTime};
-    use suiup::commands::{parse_component_with_version, BinaryName, CommandMetadata};
-    use suiup::handlers::cleanup::handle_cleanup;
+    use suiup::commands::{
+        parse_component_with_version, parse_version_spec, spec_network, BinaryName, CommandMetadata,
+    };
+    use suiup::handlers::cleanup::{
+        archives_to_prune, handle_cleanup, is_prunable, Defaults, Pins,
+    };
+    use suiup::handlers::download::parse_target;
+    use suiup::handlers::install::{
+        cargo_installed_path, ensure_writable_dir, install_summary, parse_manifest,
+        validate_also_binaries, validate_binary_alias, InstallOutcome,
+    };
     use suiup::handlers::switch::parse_binary_spec;
     use suiup::paths;
+    use suiup::types::{BinaryVersion, Config, Network, NetworkOrBranch};
     use tempfile::TempDir;
 
     #[test]
     fn test_parse_component_with_version() -> Result<(), anyhow::Error> {
-        let result = parse_component_with_version("sui")?;
+        let result = parse_component_with_version("sui", &Config::default())?;
         let expected = CommandMetadata {
             name: BinaryName::Sui,
             network: "testnet".to_string(),
@@ -22,7 +32,7 @@ mod tests {
         };
         assert_eq!(expected, result);
 
-        let result = parse_component_with_version("sui@testnet-v1.39.3")?;
+        let result = parse_component_with_version("sui@testnet-v1.39.3", &Config::default())?;
         let expected = CommandMetadata {
             name: BinaryName::Sui,
             network: "testnet".to_string(),
@@ -30,7 +40,7 @@ mod tests {
         };
         assert_eq!(expected, result,);
 
-        let result = parse_component_with_version("walrus")?;
+        let result = parse_component_with_version("walrus", &Config::default())?;
         let expected = CommandMetadata {
             name: BinaryName::Walrus,
             network: "testnet".to_string(),
@@ -38,7 +48,7 @@ mod tests {
         };
         assert_eq!(expected, result);
 
-        let result = parse_component_with_version("mvr")?;
+        let result = parse_component_with_version("mvr", &Config::default())?;
         let expected = CommandMetadata {
             name: BinaryName::Mvr,
             network: "testnet".to_string(),
@@ -46,7 +56,7 @@ mod tests {
         };
         assert_eq!(expected, result);
 
-        let result = parse_component_with_version("random");
+        let result = parse_component_with_version("random", &Config::default());
         assert_eq!(
             result.unwrap_err().to_string(),
             "Invalid binary name: random. Use `suiup list` to find available binaries to install."
@@ -56,6 +66,84 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_component_with_version_config() -> Result<()> {
+        let mut config = Config::default();
+        config
+            .default_network_for
+            .insert("sui".to_string(), "devnet".to_string());
+        config
+            .aliases
+            .insert("my-stable".to_string(), "walrus@mainnet".to_string());
+
+        let result = parse_component_with_version("sui@1.40.1", &config)?;
+        assert_eq!(result.network, "devnet");
+        assert_eq!(result.version.as_deref(), Some("1.40.1"));
+
+        let result = parse_component_with_version("my-stable", &config)?;
+        assert_eq!(result.name, BinaryName::Walrus);
+        assert_eq!(result.network, "mainnet");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_component_with_version_separators() -> Result<()> {
+        let expected = CommandMetadata {
+            name: BinaryName::Sui,
+            network: "devnet".to_string(),
+            version: Some("v1.40.1".to_string()),
+        };
+
+        for spec in [
+            "sui@devnet-v1.40.1",
+            "sui==devnet-v1.40.1",
+            "sui=devnet-v1.40.1",
+            "sui @ devnet-v1.40.1",
+            "  sui@devnet-v1.40.1  ",
+            "sui == devnet-v1.40.1",
+            "sui devnet-v1.40.1",
+        ] {
+            assert_eq!(
+                expected,
+                parse_component_with_version(spec, &Config::default())?,
+                "{spec}"
+            );
+        }
+
+        let result = parse_component_with_version("mvr 0.0.8", &Config::default())?;
+        assert_eq!(result.name, BinaryName::Mvr);
+        assert_eq!(result.version, Some("0.0.8".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_component_with_version_invalid() {
+        let err = parse_component_with_version("@testnet", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("binary name cannot be empty"));
+
+        let err = parse_component_with_version("  ", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("binary name cannot be empty"));
+
+        let err = parse_component_with_version("sui@", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("version cannot be empty"));
+
+        let err = parse_component_with_version("sui == ", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("version cannot be empty"));
+
+        let err =
+            parse_component_with_version("sui@testnet@1.40.1", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid format"));
+
+        let err =
+            parse_component_with_version("sui=testnet=1.40.1", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid format"));
+
+        let err =
+            parse_component_with_version("sui testnet 1.40.1", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid format"));
+    }
+
     #[test]
     fn test_sui_component_display() {
         assert_eq!(BinaryName::Sui.to_string(), "sui");
@@ -67,13 +155,31 @@ mod tests {
     fn test_parse_binary_spec() -> Result<()> {
         // Test valid format
         let result = parse_binary_spec("sui@testnet")?;
-        assert_eq!(result, ("sui".to_string(), "testnet".to_string()));
+        assert_eq!(result, ("sui".to_string(), "testnet".to_string(), None));
 
         let result = parse_binary_spec("mvr@main")?;
-        assert_eq!(result, ("mvr".to_string(), "main".to_string()));
+        assert_eq!(result, ("mvr".to_string(), "main".to_string(), None));
 
         let result = parse_binary_spec("walrus@devnet")?;
-        assert_eq!(result, ("walrus".to_string(), "devnet".to_string()));
+        assert_eq!(result, ("walrus".to_string(), "devnet".to_string(), None));
+
+        // Test with an exact version
+        let result = parse_binary_spec("sui@testnet-v1.39.3")?;
+        assert_eq!(
+            result,
+            (
+                "sui".to_string(),
+                "testnet".to_string(),
+                Some("v1.39.3".to_string())
+            )
+        );
+
+        let result = parse_binary_spec("sui@testnet-1.39.3")?;
+        assert_eq!(result.2, Some("v1.39.3".to_string()));
+
+        // Branch names with dashes are not mistaken for versions
+        let result = parse_binary_spec("sui@feature-x")?;
+        assert_eq!(result, ("sui".to_string(), "feature-x".to_string(), None));
 
         // Test invalid formats
         let result = parse_binary_spec("sui");
@@ -94,6 +200,20 @@ mod tests {
             .to_string()
             .contains("Binary name and network/release cannot be empty"));
 
+        let result = parse_binary_spec("sui@-v1.39.3");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Binary name and network/release cannot be empty"));
+
+        let result = parse_binary_spec("sui@testnet-");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Version cannot be empty"));
+
         let result = parse_binary_spec("sui@testnet@extra");
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid format"));
@@ -107,7 +227,7 @@ mod tests {
         std::env::set_var("XDG_CACHE_HOME", temp_dir.path());
 
         // Test cleanup on empty directory
-        let result = handle_cleanup(false, 30, true).await;
+        let result = handle_cleanup(false, 30, None, false, None, None, true, false).await;
         assert!(result.is_ok());
 
         Ok(())
@@ -133,7 +253,7 @@ mod tests {
         std::env::set_var("XDG_CACHE_HOME", temp_dir.path());
 
         // Dry run should not remove files
-        let result = handle_cleanup(false, 30, true).await;
+        let result = handle_cleanup(false, 30, None, false, None, None, true, false).await;
         assert!(result.is_ok());
         assert!(old_file.exists());
         assert!(new_file.exists());
@@ -164,7 +284,7 @@ mod tests {
         std::env::set_var("XDG_CACHE_HOME", temp_dir.path());
 
         // Actual cleanup should remove old file but keep new file
-        let result = handle_cleanup(false, 30, false).await;
+        let result = handle_cleanup(false, 30, None, false, None, None, false, false).await;
         assert!(result.is_ok());
         assert!(!old_file.exists());
         assert!(new_file.exists());
@@ -191,7 +311,7 @@ mod tests {
         std::env::set_var("XDG_CACHE_HOME", temp_dir.path());
 
         // Remove all should clear everything
-        let result = handle_cleanup(true, 30, false).await;
+        let result = handle_cleanup(true, 30, None, false, None, None, false, false).await;
         assert!(result.is_ok());
         assert!(!file1.exists());
         assert!(!file2.exists());
@@ -199,4 +319,360 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_archives_to_prune_keeps_newest_per_binary_and_network() {
+        let names: Vec<String> = [
+            "sui-testnet-v1.9.0-ubuntu-x86_64.tgz",
+            "sui-testnet-v1.10.0-ubuntu-x86_64.tgz",
+            "sui-testnet-v1.8.1-ubuntu-x86_64.tgz",
+            "sui-devnet-v1.2.0-ubuntu-x86_64.tgz",
+            "walrus-testnet-v1.18.2-ubuntu-x86_64.tgz",
+            "walrus-testnet-v1.17.1-ubuntu-x86_64.tgz",
+            "walrus-testnet-v1.17.1-ubuntu-x86_64.etag",
+            "notes.txt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let mut pruned = archives_to_prune(&names, 2);
+        pruned.sort();
+        // v1.10.0 and v1.9.0 are the newest testnet sui archives, compared numerically
+        assert_eq!(pruned, vec!["sui-testnet-v1.8.1-ubuntu-x86_64.tgz"]);
+
+        let mut pruned = archives_to_prune(&names, 1);
+        pruned.sort();
+        assert_eq!(
+            pruned,
+            vec![
+                "sui-testnet-v1.8.1-ubuntu-x86_64.tgz",
+                "sui-testnet-v1.9.0-ubuntu-x86_64.tgz",
+                "walrus-testnet-v1.17.1-ubuntu-x86_64.tgz",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_binary_alias() {
+        assert!(validate_binary_alias("sui-test").is_ok());
+        assert!(validate_binary_alias("sui_mine.2").is_ok());
+
+        assert!(validate_binary_alias("").is_err());
+        assert!(validate_binary_alias("../sui").is_err());
+        assert!(validate_binary_alias("bin/sui").is_err());
+        assert!(validate_binary_alias(".sui").is_err());
+        assert!(validate_binary_alias("-sui").is_err());
+        assert!(validate_binary_alias("sui test").is_err());
+        // would overwrite the managed binaries
+        assert!(validate_binary_alias("sui").is_err());
+        assert!(validate_binary_alias("walrus").is_err());
+    }
+
+    #[test]
+    fn test_default_network_precedence() -> Result<()> {
+        let mut config = Config::default();
+        config
+            .default_network_for
+            .insert("sui".to_string(), "mainnet".to_string());
+        // the remembered network applies to its binary only, the rest falls back to testnet
+        assert_eq!(config.default_network("sui"), "mainnet");
+        assert_eq!(config.default_network("walrus"), "testnet");
+
+        // a network in the spec always wins over the remembered one
+        let default = config.default_network("sui");
+        assert_eq!(
+            parse_version_spec(None, default)?,
+            ("mainnet".to_string(), None)
+        );
+        assert_eq!(
+            parse_version_spec(Some("1.40.1".to_string()), default)?,
+            ("mainnet".to_string(), Some("1.40.1".to_string()))
+        );
+        assert_eq!(
+            parse_version_spec(Some("devnet-1.40.1".to_string()), default)?,
+            ("devnet".to_string(), Some("1.40.1".to_string()))
+        );
+
+        assert_eq!(
+            spec_network("sui@devnet-1.40.1"),
+            Some("devnet".to_string())
+        );
+        assert_eq!(spec_network("sui@mainnet"), Some("mainnet".to_string()));
+        assert_eq!(spec_network("sui@1.40.1"), None);
+        assert_eq!(spec_network("sui"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ensure_writable_dir() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dir = temp_dir.path().join("bin");
+        ensure_writable_dir(&dir)?;
+        assert!(dir.is_dir());
+        // the probe file is removed again
+        assert_eq!(fs::read_dir(&dir)?.count(), 0);
+
+        let file = temp_dir.path().join("file");
+        fs::write(&file, "")?;
+        let err = ensure_writable_dir(&file.join("bin")).unwrap_err();
+        assert!(err.to_string().starts_with("Cannot write to"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_also_binaries() {
+        let also = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(validate_also_binaries("sui", &also(&["sui-tool", "sui-faucet"])).is_ok());
+        assert!(validate_also_binaries("sui", &[]).is_ok());
+
+        assert!(validate_also_binaries("sui", &also(&["sui"])).is_err());
+        assert!(validate_also_binaries("sui", &also(&[""])).is_err());
+        assert!(validate_also_binaries("sui", &also(&["../sui-tool"])).is_err());
+        assert!(validate_also_binaries("sui", &also(&["-sui"])).is_err());
+    }
+
+    #[test]
+    fn test_network_or_branch_round_trip() -> Result<()> {
+        for (s, expected) in [
+            ("testnet", NetworkOrBranch::Network(Network::Testnet)),
+            ("standalone", NetworkOrBranch::Standalone),
+            (
+                "releases/sui-v1.40.0",
+                NetworkOrBranch::Branch("releases/sui-v1.40.0".into()),
+            ),
+        ] {
+            let parsed: NetworkOrBranch = s.parse().unwrap();
+            assert_eq!(parsed, expected);
+            assert_eq!(parsed.to_string(), s);
+
+            let json = serde_json::to_string(&parsed)?;
+            assert_eq!(json, format!("\"{s}\""));
+            assert_eq!(serde_json::from_str::<NetworkOrBranch>(&json)?, expected);
+        }
+
+        let binary = BinaryVersion {
+            binary_name: "mvr".to_string(),
+            network_release: "standalone".to_string(),
+            version: "v0.0.8".to_string(),
+            debug: false,
+            path: None,
+            alias: None,
+            installed_at: None,
+            target: None,
+            released_at: None,
+            companions: vec![],
+        };
+        assert_eq!(binary.network(), NetworkOrBranch::Standalone);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("linux-x86_64").unwrap(), "linux-x86_64");
+        assert_eq!(parse_target("macos-arm64").unwrap(), "macos-aarch64");
+        assert_eq!(parse_target("windows-aarch64").unwrap(), "windows-aarch64");
+
+        assert!(parse_target("linux").is_err());
+        assert!(parse_target("ubuntu-x86_64").is_err());
+        assert!(parse_target("linux-riscv64").is_err());
+    }
+
+    #[test]
+    fn test_is_prunable() {
+        let binary = |name: &str, network: &str, version: &str| BinaryVersion {
+            binary_name: name.to_string(),
+            network_release: network.to_string(),
+            version: version.to_string(),
+            debug: false,
+            path: None,
+            alias: None,
+            installed_at: None,
+            target: None,
+            released_at: None,
+            companions: vec![],
+        };
+        let mut defaults = Defaults::new();
+        defaults.insert(
+            "sui".to_string(),
+            ("testnet".to_string(), "v1.40.1".to_string(), false),
+        );
+        let mut pins = Pins::new();
+        pins.insert(
+            "walrus".to_string(),
+            ("mainnet".to_string(), Some("1.18.2".to_string())),
+        );
+
+        assert!(!is_prunable(
+            &binary("sui", "testnet", "v1.40.1"),
+            &defaults,
+            &pins
+        ));
+        assert!(is_prunable(
+            &binary("sui", "testnet", "v1.39.3"),
+            &defaults,
+            &pins
+        ));
+        assert!(is_prunable(
+            &binary("sui", "devnet", "v1.40.1"),
+            &defaults,
+            &pins
+        ));
+        assert!(!is_prunable(
+            &binary("walrus", "mainnet", "v1.18.2"),
+            &defaults,
+            &pins
+        ));
+        assert!(is_prunable(
+            &binary("walrus", "testnet", "v1.18.2"),
+            &defaults,
+            &pins
+        ));
+
+        let mut debug = binary("sui", "testnet", "v1.40.1");
+        debug.debug = true;
+        assert!(is_prunable(&debug, &defaults, &pins));
+
+        // a pin without a version keeps the whole network
+        pins.insert("sui".to_string(), ("devnet".to_string(), None));
+        assert!(!is_prunable(
+            &binary("sui", "devnet", "v1.39.0"),
+            &defaults,
+            &pins
+        ));
+    }
+
+    #[test]
+    fn test_install_summary() {
+        let binary = std::path::Path::new("/data/binaries/testnet/sui-v1.40.1");
+        let summary = install_summary(
+            "sui",
+            "v1.40.1",
+            binary,
+            Some(("sui", "/home/me/.local/bin/sui".into())),
+        );
+        assert_eq!(
+            summary,
+            "\nInstalled sui v1.40.1 to /data/binaries/testnet/sui-v1.40.1\n\
+             Default: /home/me/.local/bin/sui\n\
+             Run it with: sui --version\n"
+        );
+
+        let summary = install_summary("sui", "v1.40.1", binary, None);
+        assert!(summary.contains("Run it with: /data/binaries/testnet/sui-v1.40.1 --version"));
+        assert!(!summary.contains("Default:"));
+    }
+
+    #[test]
+    fn test_cargo_installed_path() {
+        let stderr = "    Updating git repository `https://github.com/MystenLabs/sui`
+  Installing sui v1.41.0 (https://github.com/MystenLabs/sui?branch=main#0a1b2c3d)
+   Compiling sui v1.41.0 (/root/.cargo/git/checkouts/sui-1234/0a1b2c3/crates/sui)
+    Finished `release` profile [optimized] target(s) in 9m 12s
+  Installing /opt/cargo/sui/bin/sui
+   Installed package `sui v1.41.0` (executable `sui`)
+";
+        assert_eq!(
+            cargo_installed_path(stderr, "sui"),
+            Some("/opt/cargo/sui/bin/sui".into())
+        );
+        assert_eq!(cargo_installed_path(stderr, "walrus"), None);
+
+        // with --force, an existing binary is replaced instead
+        let stderr = "  Replacing /opt/cargo/main/bin/walrus
+    Replaced package `walrus-service v1.20.0` with `walrus-service v1.21.0` (executable `walrus`)
+";
+        assert_eq!(
+            cargo_installed_path(stderr, "walrus"),
+            Some("/opt/cargo/main/bin/walrus".into())
+        );
+
+        // older cargo versions only print the crate line
+        assert_eq!(
+            cargo_installed_path("  Installing sui v1.41.0\n", "sui"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_install_outcome_summary() {
+        let outcome = InstallOutcome {
+            binary: "sui".to_string(),
+            version: "v1.40.1".to_string(),
+            network: "testnet".to_string(),
+            newly_installed: true,
+            set_default: false,
+            path: "/data/binaries/testnet/sui-v1.40.1".into(),
+            default_path: Some("/home/me/.local/bin/sui-test".into()),
+            alias: Some("sui-test".to_string()),
+            target: None,
+        };
+        assert!(outcome
+            .summary()
+            .contains("Default: /home/me/.local/bin/sui-test\nRun it with: sui-test --version"));
+
+        let present = InstallOutcome {
+            newly_installed: false,
+            default_path: None,
+            alias: None,
+            ..outcome.clone()
+        };
+        assert_eq!(
+            present.summary(),
+            "Binary sui-v1.40.1 already installed. Use `suiup default set` to change the default binary.\n"
+        );
+
+        let downloaded = InstallOutcome {
+            target: Some("macos-aarch64".to_string()),
+            default_path: None,
+            alias: None,
+            ..outcome
+        };
+        assert_eq!(
+            downloaded.summary(),
+            "Downloaded sui-v1.40.1 for macos-aarch64 to /data/binaries/testnet/sui-v1.40.1\n"
+        );
+        assert_eq!(
+            InstallOutcome {
+                newly_installed: false,
+                ..downloaded
+            }
+            .summary(),
+            "Binary sui-v1.40.1 for macos-aarch64 already downloaded\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest =
+            "# team tools\nsui@testnet-1.40.1\n\n  mvr@0.0.5  \nwalrus@testnet # storage\n";
+        assert_eq!(
+            parse_manifest(manifest, &Config::default()).unwrap(),
+            vec!["sui@testnet-1.40.1", "mvr@0.0.5", "walrus@testnet"]
+        );
+
+        let err = parse_manifest("sui\nfoo@testnet\n", &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(parse_manifest("# nothing here\n\n", &Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_to_posix() {
+        use std::path::Path;
+
+        assert_eq!(
+            paths::to_posix(Path::new("C:\\Users\\x\\bin")),
+            "/c/Users/x/bin"
+        );
+        assert_eq!(paths::to_posix(Path::new("d:\\")), "/d/");
+        assert_eq!(paths::to_posix(Path::new("C:")), "/c");
+        assert_eq!(
+            paths::to_posix(Path::new("\\\\server\\share\\bin")),
+            "//server/share/bin"
+        );
+        assert_eq!(
+            paths::to_posix(Path::new("/home/x/.local/bin")),
+            "/home/x/.local/bin"
+        );
+    }
 }
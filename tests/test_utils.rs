@@ -2,9 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use flate2::{write::GzEncoder, Compression};
 use lazy_static::lazy_static;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
 use std::path::PathBuf;
-use std::{env, sync::Mutex};
+use std::{
+    env,
+    sync::{Mutex, MutexGuard},
+};
 use suiup::paths::{
     get_cache_home, get_config_home, get_data_home, get_default_bin_dir, initialize,
 };
@@ -17,23 +24,28 @@ pub struct TestEnv {
     pub config_dir: PathBuf,
     pub cache_dir: PathBuf,
     pub bin_dir: PathBuf,
-    original_env: Vec<(String, String)>,
+    original_env: Vec<(&'static str, Option<String>)>,
+    // the dirs are derived from the process environment, which every test changes
+    _env_guard: MutexGuard<'static, ()>,
 }
 
 lazy_static! {
     static ref ZIP_FILES_MUTEX: Mutex<()> = Mutex::new(());
+    static ref ENV_MUTEX: Mutex<()> = Mutex::new(());
 }
 
 impl TestEnv {
     pub fn new() -> Result<Self> {
+        // a test that panicked still restored the environment when its TestEnv was dropped
+        let env_guard = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
         let temp_dir = TempDir::new()?;
         let base = temp_dir.path();
 
         let home_dir = dirs::home_dir().unwrap();
 
-        let data_home = get_data_home();
-        let config_home = get_config_home();
-        let cache_home = get_cache_home();
+        let data_home = get_data_home()?;
+        let config_home = get_config_home()?;
+        let cache_home = get_cache_home()?;
         let bin_home = get_default_bin_dir();
 
         let data_dir = if let Ok(path) = data_home.strip_prefix(&home_dir) {
@@ -83,7 +95,7 @@ impl TestEnv {
 
         let original_env = vars_to_capture
             .into_iter()
-            .filter_map(|var| env::var(var).ok().map(|val| (var.to_string(), val)))
+            .map(|var| (var, env::var(var).ok()))
             .collect();
 
         // Set test env vars
@@ -111,6 +123,7 @@ impl TestEnv {
             cache_dir,
             bin_dir,
             original_env,
+            _env_guard: env_guard,
         })
     }
 
@@ -118,6 +131,32 @@ impl TestEnv {
         initialize()
     }
 
+    /// Writes `installed_binaries.json` with the given entries, see [`installed`]
+    pub fn write_installed(&self, binaries: &[Value]) -> Result<()> {
+        std::fs::write(
+            self.config_dir
+                .join("suiup")
+                .join("installed_binaries.json"),
+            serde_json::to_string(&json!({ "binaries": binaries }))?,
+        )?;
+        Ok(())
+    }
+
+    /// Writes `default_version.json` with `(binary, network, version, debug)` entries
+    pub fn write_defaults(&self, defaults: &[(&str, &str, &str, bool)]) -> Result<()> {
+        let defaults: serde_json::Map<String, Value> = defaults
+            .iter()
+            .map(|(binary, network, version, debug)| {
+                (binary.to_string(), json!([network, version, debug]))
+            })
+            .collect();
+        std::fs::write(
+            self.config_dir.join("suiup").join("default_version.json"),
+            serde_json::to_string(&defaults)?,
+        )?;
+        Ok(())
+    }
+
     pub fn copy_testnet_releases_to_cache(&self) -> Result<()> {
         let _guard = ZIP_FILES_MUTEX.lock().unwrap();
         // Create cache directory if it doesn't exist
@@ -167,11 +206,61 @@ impl TestEnv {
     }
 }
 
+/// Serves a `.tgz` release archive holding `members` (name and content) over HTTP on localhost,
+/// for every request, until the test process exits. Returns the URL of the archive.
+pub fn serve_release_archive(members: &[(&str, &[u8])]) -> Result<String> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (name, content) in members {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, name, *content)?;
+    }
+    let archive = builder.into_inner()?.finish()?;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/release.tgz", listener.local_addr()?);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            // the request line and headers end with an empty line
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                line.clear();
+            }
+            let mut stream = &stream;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                archive.len()
+            );
+            let _ = stream.write_all(&archive);
+        }
+    });
+    Ok(url)
+}
+
+/// An entry of `installed_binaries.json` for a release build kept at its default path. Other
+/// fields, like `debug`, `path` or `installed_at`, can be set on the returned value.
+pub fn installed(binary: &str, network: &str, version: &str) -> Value {
+    json!({
+        "binary_name": binary,
+        "network_release": network,
+        "version": version,
+        "debug": false,
+        "path": null,
+    })
+}
+
 impl Drop for TestEnv {
     fn drop(&mut self) {
-        // Restore original env vars
+        // Restore original env vars, and unset the ones that were not set
         for (var, val) in &self.original_env {
-            env::set_var(var, val);
+            match val {
+                Some(val) => env::set_var(var, val),
+                None => env::remove_var(var),
+            }
         }
     }
 }